@@ -7,6 +7,17 @@ use crate::{
     heaven_client::HeavenClient,
     database::Database,
     monitoring::Metrics,
+    positions::PositionManager,
+    risk::{Halt, RiskEngine},
+    rebalancer::Rebalancer,
+    fade::FadeFeed,
+    sizing::PositionSizer,
+    strategy::{Strategy, StrategyRuntime},
+    database::metrics::MetricsSlice,
+    types::{
+        BotEvent, CopySlippageBreakdown, CopySlippageGroup, DailyStats, PerformanceMetrics,
+        PnlBreakdown, PnlGroup, TraderStatus, TraderStatusChange,
+    },
 };
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -14,7 +25,7 @@ use solana_sdk::{
     pubkey::Pubkey,
 };
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{info, warn, error};
 
 pub struct HeavenTradingBot {
@@ -23,9 +34,14 @@ pub struct HeavenTradingBot {
     heaven_client: Arc<HeavenClient>,
     database: Arc<Database>,
     metrics: Arc<Metrics>,
+    positions: Arc<PositionManager>,
+    risk: Arc<RiskEngine>,
     sniper_bot: Option<Arc<SniperBot>>,
     copy_trader_bot: Option<Arc<CopyTraderBot>>,
     bundler_bot: Option<Arc<BundlerBot>>,
+    strategy_runtime: Arc<StrategyRuntime>,
+    rebalancer: Option<Arc<Rebalancer>>,
+    fade: Arc<FadeFeed>,
     wallet: Arc<Keypair>,
     is_running: Arc<RwLock<bool>>,
 }
@@ -56,6 +72,32 @@ impl HeavenTradingBot {
         // Initialize metrics
         let metrics = Arc::new(Metrics::new(&config.monitoring)?);
         
+        // Initialize shared position tracking
+        let positions = Arc::new(PositionManager::new(
+            database.clone(),
+            heaven_client.clone(),
+            config.trading.cost_basis,
+        ));
+        
+        // Initialize portfolio risk checks shared by every buy path
+        let risk = Arc::new(RiskEngine::new(
+            config.trading.clone(),
+            database.clone(),
+            heaven_client.clone(),
+            positions.clone(),
+            metrics.clone(),
+        ));
+        
+        // Entry sizing shared by the sniper and copy trader
+        let sizer = Arc::new(PositionSizer::new(
+            config.trading.clone(),
+            heaven_client.clone(),
+            positions.clone(),
+        ));
+        
+        // Tokens bought by faded wallets, shared by the copy trader and sniper
+        let fade = Arc::new(FadeFeed::new(&config.copy_trader, heaven_client.clone(), database.clone()));
+        
         // Initialize component bots
         let sniper_bot = if config.sniper.enabled {
             Some(Arc::new(SniperBot::new(
@@ -64,6 +106,10 @@ impl HeavenTradingBot {
                 heaven_client.clone(),
                 database.clone(),
                 metrics.clone(),
+                positions.clone(),
+                risk.clone(),
+                sizer.clone(),
+                fade.clone(),
                 wallet.clone(),
             )?))
         } else {
@@ -77,6 +123,10 @@ impl HeavenTradingBot {
                 heaven_client.clone(),
                 database.clone(),
                 metrics.clone(),
+                positions.clone(),
+                risk.clone(),
+                sizer.clone(),
+                fade.clone(),
                 wallet.clone(),
             )?))
         } else {
@@ -90,21 +140,55 @@ impl HeavenTradingBot {
                 heaven_client.clone(),
                 database.clone(),
                 metrics.clone(),
+                risk.clone(),
+                positions.clone(),
                 wallet.clone(),
             )?))
         } else {
             None
         };
         
+        // Initialize runtime for user-registered strategies
+        let strategy_runtime = Arc::new(StrategyRuntime::new(
+            config.clone(),
+            rpc_client.clone(),
+            heaven_client.clone(),
+            database.clone(),
+            metrics.clone(),
+            positions.clone(),
+            risk.clone(),
+            wallet.clone(),
+        ));
+        
+        let rebalancer = if config.trading.auto_rebalance {
+            Some(Arc::new(Rebalancer::new(
+                config.clone(),
+                rpc_client.clone(),
+                heaven_client.clone(),
+                database.clone(),
+                metrics.clone(),
+                positions.clone(),
+                risk.clone(),
+                wallet.clone(),
+            )))
+        } else {
+            None
+        };
+        
         Ok(Self {
             config,
             rpc_client,
             heaven_client,
             database,
             metrics,
+            positions,
+            risk,
             sniper_bot,
             copy_trader_bot,
             bundler_bot,
+            strategy_runtime,
+            rebalancer,
+            fade,
             wallet,
             is_running: Arc::new(RwLock::new(false)),
         })
@@ -121,9 +205,28 @@ impl HeavenTradingBot {
             self.metrics.start().await?;
         }
         
+        // Rebuild open positions before any bot can trade
+        self.positions.recover().await?;
+        
+        // A halt from before the restart keeps blocking entries
+        self.risk.load().await?;
+        
         // Start all component bots
         let mut handles = Vec::new();
         
+        // Faded wallets are followed for the sniper and copy trader alike
+        if self.fade.enabled() {
+            self.fade.load().await?;
+            let fade = self.fade.clone();
+            let is_running = self.is_running.clone();
+            let handle = tokio::spawn(async move {
+                if let Err(e) = fade.run(is_running).await {
+                    error!("Fade feed error: {}", e);
+                }
+            });
+            handles.push(handle);
+        }
+        
         if let Some(sniper_bot) = &self.sniper_bot {
             let sniper = sniper_bot.clone();
             let handle = tokio::spawn(async move {
@@ -154,6 +257,28 @@ impl HeavenTradingBot {
             handles.push(handle);
         }
         
+        // Start the strategy runtime
+        let strategy_runtime = self.strategy_runtime.clone();
+        let is_running = self.is_running.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = strategy_runtime.run(is_running).await {
+                error!("Strategy runtime error: {}", e);
+            }
+        });
+        handles.push(handle);
+        
+        // Start the rebalancer
+        if let Some(rebalancer) = &self.rebalancer {
+            let rebalancer = rebalancer.clone();
+            let is_running = self.is_running.clone();
+            let handle = tokio::spawn(async move {
+                if let Err(e) = rebalancer.run(is_running).await {
+                    error!("Rebalancer error: {}", e);
+                }
+            });
+            handles.push(handle);
+        }
+        
         // Start main trading loop
         let main_handle = tokio::spawn({
             let is_running = self.is_running.clone();
@@ -161,9 +286,11 @@ impl HeavenTradingBot {
             let heaven_client = self.heaven_client.clone();
             let database = self.database.clone();
             let metrics = self.metrics.clone();
+            let positions = self.positions.clone();
+            let risk = self.risk.clone();
             
             async move {
-                Self::main_trading_loop(is_running, config, heaven_client, database, metrics).await
+                Self::main_trading_loop(is_running, config, heaven_client, database, metrics, positions, risk).await
             }
         });
         handles.push(main_handle);
@@ -178,6 +305,72 @@ impl HeavenTradingBot {
         Ok(())
     }
     
+    /// Register a user strategy; it is driven alongside the built-in bots once `start` runs.
+    pub async fn register_strategy(&self, strategy: Box<dyn Strategy>) -> Result<(), BotError> {
+        self.strategy_runtime.register(strategy).await
+    }
+    
+    pub fn strategy_runtime(&self) -> Arc<StrategyRuntime> {
+        self.strategy_runtime.clone()
+    }
+    
+    /// Realized and unrealized PnL from the cost-basis ledger.
+    pub async fn pnl_report(&self, group: PnlGroup) -> Result<Vec<PnlBreakdown>, BotError> {
+        self.positions.ledger().pnl_by(group).await
+    }
+    
+    /// Copy fills against the leader's fills, grouped by trader or token.
+    pub async fn copy_slippage_report(
+        &self,
+        group: CopySlippageGroup,
+    ) -> Result<Vec<CopySlippageBreakdown>, BotError> {
+        self.database.get_copy_slippage_report(group).await
+    }
+    
+    /// Tracked trader status changes, newest first.
+    pub async fn trader_status_history(
+        &self,
+        trader_address: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TraderStatusChange>, BotError> {
+        self.database.get_trader_status_history(trader_address, limit).await
+    }
+    
+    /// Override a tracked trader's status, e.g. to reinstate a blacklisted trader.
+    pub async fn set_trader_status(&self, address: &str, status: TraderStatus, reason: &str) -> Result<(), BotError> {
+        match &self.copy_trader_bot {
+            Some(copy_trader) => copy_trader.set_trader_status(address, status, reason).await,
+            None => Err(BotError::Validation("Copy trader is not enabled".to_string())),
+        }
+    }
+    
+    pub async fn performance_metrics(&self, slice: &MetricsSlice) -> Option<PerformanceMetrics> {
+        self.positions.ledger().performance(slice).await
+    }
+    
+    pub async fn daily_stats(&self) -> Vec<DailyStats> {
+        self.positions.ledger().daily_stats().await
+    }
+    
+    /// Halt all new entries and sell every open position.
+    pub async fn kill_switch(&self, reason: &str) {
+        self.risk.kill(reason).await;
+    }
+    
+    /// Lift a circuit breaker or kill switch halt.
+    pub async fn resume_trading(&self) {
+        self.risk.resume().await;
+    }
+    
+    pub async fn halt_status(&self) -> Option<Halt> {
+        self.risk.halt().await
+    }
+    
+    /// Risk events such as circuit breaker trips, and tracked trader status changes.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<BotEvent> {
+        self.risk.subscribe()
+    }
+    
     pub async fn stop(&mut self) -> Result<(), BotError> {
         info!("Stopping Heaven Trading Bot...");
         *self.is_running.write().await = false;
@@ -196,6 +389,8 @@ impl HeavenTradingBot {
         heaven_client: Arc<HeavenClient>,
         database: Arc<Database>,
         metrics: Arc<Metrics>,
+        positions: Arc<PositionManager>,
+        risk: Arc<RiskEngine>,
     ) -> Result<(), BotError> {
         let mut interval = tokio::time::interval(
             std::time::Duration::from_secs(config.monitoring.health_check_interval_secs)
@@ -208,13 +403,36 @@ impl HeavenTradingBot {
             if let Err(e) = Self::health_check(&heaven_client).await {
                 warn!("Health check failed: {}", e);
                 metrics.record_health_check_failure().await;
+                risk.record_rpc_result(false).await;
             } else {
                 metrics.record_health_check_success().await;
+                risk.record_rpc_result(true).await;
             }
             
-            // Update metrics
-            if let Ok(balance) = heaven_client.get_sol_balance().await {
-                metrics.update_sol_balance(balance).await;
+            // Mark open positions to market
+            if let Err(e) = positions.refresh_prices().await {
+                warn!("Position price refresh failed: {}", e);
+            }
+            
+            // Update metrics and check the balance and drawdown breakers
+            match heaven_client.get_sol_balance().await {
+                Ok(balance) => {
+                    risk.record_rpc_result(true).await;
+                    metrics.update_sol_balance(balance).await;
+                    risk.record_portfolio(balance, balance + positions.market_value().await).await;
+                }
+                Err(e) => {
+                    warn!("Balance check failed: {}", e);
+                    risk.record_rpc_result(false).await;
+                }
+            }
+            
+            // Export performance from closed trades
+            if let Some(performance) = positions.ledger().performance(&MetricsSlice::All).await {
+                metrics.set_gauge("realized_pnl_sol", performance.total_pnl).await;
+                metrics.set_gauge("win_rate", performance.win_rate).await;
+                metrics.set_gauge("max_drawdown_sol", performance.max_drawdown).await;
+                metrics.set_gauge("sharpe_ratio", performance.sharpe_ratio).await;
             }
             
             // Check for new opportunities
@@ -227,15 +445,9 @@ impl HeavenTradingBot {
     }
     
     async fn health_check(heaven_client: &HeavenClient) -> Result<(), BotError> {
-        // Check if we can connect to Heaven
+        // Check if we can connect to Heaven; the balance floor is a circuit breaker
         heaven_client.ping().await?;
         
-        // Check if we have sufficient balance
-        let balance = heaven_client.get_sol_balance().await?;
-        if balance < 0.01 {
-            return Err(BotError::InsufficientBalance("Low SOL balance".to_string()));
-        }
-        
         Ok(())
     }
     
@@ -281,6 +493,9 @@ impl HeavenTradingBot {
             sol_balance: self.heaven_client.get_sol_balance().await.unwrap_or(0.0),
             total_trades: self.database.get_total_trades().await.unwrap_or(0),
             daily_pnl: self.database.get_daily_pnl().await.unwrap_or(0.0),
+            open_positions: self.positions.open_positions().await.len(),
+            unrealized_pnl: self.positions.total_unrealized_pnl().await,
+            halt: self.risk.halt().await,
         }
     }
 }
@@ -294,4 +509,7 @@ pub struct BotStatus {
     pub sol_balance: f64,
     pub total_trades: u64,
     pub daily_pnl: f64,
+    pub open_positions: usize,
+    pub unrealized_pnl: f64,
+    pub halt: Option<Halt>,
 }
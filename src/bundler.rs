@@ -4,7 +4,9 @@ use crate::{
     heaven_client::HeavenClient,
     database::Database,
     monitoring::Metrics,
-    types::{Bundle, BundleTransaction, BundleResult},
+    positions::{new_position, PositionManager},
+    risk::{RiskEngine, RiskPermit},
+    types::{Bundle, BundleTransaction, BundleResult, SniperStrategy},
 };
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -28,12 +30,16 @@ pub struct BundlerBot {
     heaven_client: Arc<HeavenClient>,
     database: Arc<Database>,
     metrics: Arc<Metrics>,
+    risk: Arc<RiskEngine>,
+    positions: Arc<PositionManager>,
     wallet: Arc<Keypair>,
     is_running: Arc<RwLock<bool>>,
     pending_bundles: Arc<RwLock<Vec<Bundle>>>,
     active_bundles: Arc<RwLock<HashMap<String, Bundle>>>,
     bundle_history: Arc<RwLock<Vec<BundleResult>>>,
     last_bundle_time: Arc<RwLock<Instant>>,
+    // Risk reservations for buys, keyed by transaction id, held until their bundle lands or fails
+    risk_permits: Arc<RwLock<HashMap<String, RiskPermit>>>,
 }
 
 impl BundlerBot {
@@ -43,6 +49,8 @@ impl BundlerBot {
         heaven_client: Arc<HeavenClient>,
         database: Arc<Database>,
         metrics: Arc<Metrics>,
+        risk: Arc<RiskEngine>,
+        positions: Arc<PositionManager>,
         wallet: Arc<Keypair>,
     ) -> Result<Self, BotError> {
         Ok(Self {
@@ -51,12 +59,15 @@ impl BundlerBot {
             heaven_client,
             database,
             metrics,
+            risk,
+            positions,
             wallet,
             is_running: Arc::new(RwLock::new(false)),
             pending_bundles: Arc::new(RwLock::new(Vec::new())),
             active_bundles: Arc::new(RwLock::new(HashMap::new())),
             bundle_history: Arc<RwLock::new(Vec::new())),
             last_bundle_time: Arc<RwLock::new(Instant::now()),
+            risk_permits: Arc::new(RwLock::new(HashMap::new())),
         })
     }
     
@@ -107,6 +118,25 @@ impl BundlerBot {
     }
     
     pub async fn add_transaction_to_bundle(&self, transaction: BundleTransaction) -> Result<(), BotError> {
+        // Buys have to clear the portfolio limits before they are queued
+        match transaction.trade_type.as_str() {
+            "buy" => {
+                if transaction.token_mint.is_empty() || transaction.amount_sol <= 0.0 {
+                    return Err(BotError::Validation(format!(
+                        "Bundle buy {} needs a token mint and a positive SOL amount", transaction.id
+                    )));
+                }
+                let permit = self.risk.check_buy("bundler", &transaction.token_mint, transaction.amount_sol).await?;
+                self.risk_permits.write().await.insert(transaction.id.clone(), permit);
+            }
+            "sell" => {}
+            other => {
+                return Err(BotError::Validation(format!(
+                    "Unknown bundle trade type {} for {}", other, transaction.id
+                )));
+            }
+        }
+        
         let mut pending_bundles = self.pending_bundles.write().await;
         
         // Find an existing bundle or create a new one
@@ -164,6 +194,7 @@ impl BundlerBot {
                     Err(e) => {
                         error!("Failed to submit bundle {}: {}", bundle.id, e);
                         bundle.status = "failed".to_string();
+                        self.release_risk_permits(bundle).await;
                         to_remove.push(index);
                     }
                 }
@@ -334,6 +365,9 @@ impl BundlerBot {
                         
                         info!("Bundle {} confirmed", bundle_id);
                         self.metrics.record_successful_bundle(bundle.transactions.len()).await;
+                        if let Err(e) = self.open_bundle_positions(bundle, signature).await {
+                            error!("Failed to record positions for bundle {}: {}", bundle_id, e);
+                        }
                     } else if status.is_err() {
                         bundle.status = "failed".to_string();
                         to_remove.push(bundle_id.clone());
@@ -347,12 +381,68 @@ impl BundlerBot {
         
         // Remove completed bundles
         for bundle_id in to_remove {
-            active_bundles.remove(&bundle_id);
+            if let Some(bundle) = active_bundles.remove(&bundle_id) {
+                self.release_risk_permits(&bundle).await;
+            }
         }
         
         Ok(())
     }
     
+    /// Open a position for every buy in a confirmed bundle. Buys of the same
+    /// token share its fill in proportion to the SOL they spent.
+    async fn open_bundle_positions(&self, bundle: &Bundle, signature: &str) -> Result<(), BotError> {
+        let buys: Vec<&BundleTransaction> = bundle.transactions.iter()
+            .filter(|t| t.trade_type == "buy")
+            .collect();
+        if buys.is_empty() {
+            return Ok(());
+        }
+        
+        let fee_share = 1.0 / buys.len() as f64;
+        let mut fills = HashMap::new();
+        for transaction in &buys {
+            if !fills.contains_key(&transaction.token_mint) {
+                let fill = self.heaven_client.get_fill(signature, &transaction.token_mint).await?;
+                fills.insert(transaction.token_mint.clone(), fill);
+            }
+        }
+        
+        for transaction in buys {
+            let fill = &fills[&transaction.token_mint];
+            let spent_on_token: f64 = bundle.transactions.iter()
+                .filter(|t| t.trade_type == "buy" && t.token_mint == transaction.token_mint)
+                .map(|t| t.amount_sol)
+                .sum();
+            let token_amount = fill.token_amount() * transaction.amount_sol / spent_on_token;
+            if token_amount <= 0.0 {
+                warn!("Bundle buy {} of {} received no tokens", transaction.id, transaction.token_mint);
+                continue;
+            }
+            
+            let fee_sol = fill.fee_sol * fee_share;
+            let cost = transaction.amount_sol + fee_sol;
+            self.positions.open_position(new_position(
+                transaction.id.clone(),
+                &transaction.token_mint,
+                "bundler",
+                SniperStrategy::Custom("Bundle".to_string()),
+                cost / token_amount,
+                cost,
+                token_amount,
+            ), fee_sol).await?;
+        }
+        
+        Ok(())
+    }
+    
+    async fn release_risk_permits(&self, bundle: &Bundle) {
+        let mut risk_permits = self.risk_permits.write().await;
+        for transaction in &bundle.transactions {
+            risk_permits.remove(&transaction.id);
+        }
+    }
+    
     async fn cleanup_completed_bundles(&self) -> Result<(), BotError> {
         let mut bundle_history = self.bundle_history.write().await;
         
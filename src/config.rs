@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use crate::error::BotError;
+use crate::types::{SnipePriority, SnipeTarget};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotConfig {
@@ -13,6 +14,10 @@ pub struct BotConfig {
     pub trading: TradingConfig,
     pub database: DatabaseConfig,
     pub monitoring: MonitoringConfig,
+    #[serde(default)]
+    pub strategy_runtime: StrategyRuntimeConfig,
+    #[serde(default)]
+    pub scripting: ScriptingConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +56,178 @@ pub struct SniperConfig {
     pub max_market_cap: f64,
     pub volume_threshold: f64,
     pub launch_detection_delay_ms: u64,
+    #[serde(default)]
+    pub verified_creators: Vec<String>,
+    #[serde(default)]
+    pub creators: CreatorReputationConfig,
+    #[serde(default)]
+    pub queue: LaunchQueueConfig,
+    #[serde(default)]
+    pub watchlist: Vec<SnipeTarget>,
+    #[serde(default)]
+    pub strategies: Vec<StrategyDefinition>,
+}
+
+/// Matched launches wait in a priority queue for a bounded pool of snipe workers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchQueueConfig {
+    /// Snipes executed concurrently
+    pub workers: usize,
+    /// Queued launches beyond this evict the lowest priority one
+    pub capacity: usize,
+    /// Launches older than this are dropped instead of sniped
+    pub max_launch_age_ms: u64,
+}
+
+impl Default for LaunchQueueConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            capacity: 256,
+            max_launch_age_ms: 5000,
+        }
+    }
+}
+
+/// Tracks how each creator's launches turned out and scores creators on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreatorReputationConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// How long a launch is watched before its outcome is settled
+    pub watch_hours: u64,
+    /// A fall of this share from the peak price counts as a rug
+    pub rug_drawdown: f64,
+    /// Score of creators without settled launches
+    pub unknown_score: f64,
+    /// Skip launches by creators scoring below this
+    pub min_score: Option<f64>,
+    /// Creators with this many rugs are blacklisted
+    pub blacklist_rugs: u64,
+    /// `CreatorVerified` also passes for creators with this score ...
+    pub verified_min_score: f64,
+    /// ... over at least this many settled launches
+    pub verified_min_launches: u64,
+}
+
+impl Default for CreatorReputationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 60,
+            watch_hours: 24,
+            rug_drawdown: 0.8,
+            unknown_score: 0.5,
+            min_score: None,
+            blacklist_rugs: 2,
+            verified_min_score: 0.8,
+            verified_min_launches: 3,
+        }
+    }
+}
+
+/// A sniper strategy declared in config as a `[[sniper.strategies]]` table.
+/// Filters are simple `field op value` expressions over `TokenLaunch` fields,
+/// e.g. `"market_cap < 10000"` or `"token_type == creator"`; all must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyDefinition {
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub filters: Vec<String>,
+    #[serde(default = "default_size_multiplier")]
+    pub size_multiplier: f64,
+    pub max_slippage: Option<f64>,
+    /// Overrides `trading.sizing.policy` for this strategy
+    #[serde(default)]
+    pub sizing: Option<SizingPolicy>,
+    #[serde(default)]
+    pub exit: ExitRules,
+    /// Queue priority of launches this strategy matches
+    #[serde(default)]
+    pub priority: SnipePriority,
+}
+
+/// Per-strategy exit overrides. Unset values fall back to `[trading]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExitRules {
+    pub take_profit: Option<f64>,
+    pub stop_loss: Option<f64>,
+    pub max_hold_secs: Option<u64>,
+    /// Sell everything once price falls this fraction below the high-water mark
+    pub trailing_stop: Option<f64>,
+    /// Partial take-profits; replaces `take_profit` when non-empty
+    #[serde(default)]
+    pub take_profit_ladder: Vec<TakeProfitLevel>,
+    /// Raise the stop to the entry price once the first take-profit fills
+    #[serde(default)]
+    pub break_even_after_first_tp: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeProfitLevel {
+    /// Price multiple of entry, e.g. 2.0 for a 2x
+    pub multiple: f64,
+    /// Fraction of the original position to sell at this level
+    pub fraction: f64,
+}
+
+impl ExitRules {
+    fn validate(&self, owner: &str) -> Result<(), BotError> {
+        if let Some(trailing_stop) = self.trailing_stop {
+            if trailing_stop <= 0.0 || trailing_stop >= 1.0 {
+                return Err(BotError::Validation(format!("{}: trailing stop must be between 0 and 1", owner)));
+            }
+        }
+        
+        let mut last_multiple = 1.0;
+        let mut total_fraction = 0.0;
+        for level in &self.take_profit_ladder {
+            if level.multiple <= last_multiple {
+                return Err(BotError::Validation(format!(
+                    "{}: take-profit multiples must be above 1.0 and increasing", owner
+                )));
+            }
+            if level.fraction <= 0.0 || level.fraction > 1.0 {
+                return Err(BotError::Validation(format!("{}: take-profit fractions must be in (0, 1]", owner)));
+            }
+            last_multiple = level.multiple;
+            total_fraction += level.fraction;
+        }
+        
+        if total_fraction > 1.0 + 1e-9 {
+            return Err(BotError::Validation(format!("{}: take-profit fractions sum to more than 1", owner)));
+        }
+        
+        Ok(())
+    }
+}
+
+fn default_max_token_exposure() -> f64 {
+    0.25
+}
+
+fn default_max_position_fraction() -> f64 {
+    0.1
+}
+
+fn default_kelly_min_fraction() -> f64 {
+    0.005
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_copy_slippage() -> f64 {
+    0.1
+}
+
+fn default_size_multiplier() -> f64 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +242,237 @@ pub struct CopyTraderConfig {
     pub whitelisted_traders: Vec<String>,
     pub delay_ms: u64,
     pub auto_approve: bool,
+    #[serde(default = "default_copy_slippage")]
+    pub max_slippage: f64,
+    /// Follow tracked wallets over WebSocket log subscriptions; polling every
+    /// `delay_ms` then only backfills anything the subscriptions missed
+    #[serde(default = "default_true")]
+    pub realtime: bool,
+    #[serde(default)]
+    pub exit: ExitRules,
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    #[serde(default)]
+    pub sizing_mode: CopySizingMode,
+    /// Per-trader overrides, keyed by wallet address
+    #[serde(default)]
+    pub traders: HashMap<String, TraderCopyConfig>,
+    #[serde(default)]
+    pub weighting: CopyWeightingConfig,
+    #[serde(default)]
+    pub consensus: ConsensusConfig,
+    #[serde(default)]
+    pub fade: FadeConfig,
+    #[serde(default)]
+    pub evaluation: TraderEvaluationConfig,
+}
+
+/// Watch consistently losing wallets and stay out of, or sell, what they buy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FadeConfig {
+    pub enabled: bool,
+    /// Wallets to fade besides those found by discovery
+    pub wallets: Vec<String>,
+    /// Also fade `blacklisted_traders`
+    pub fade_blacklisted: bool,
+    /// How long a token is avoided after a faded wallet buys it
+    pub cooldown_secs: u64,
+    /// Sell our inventory of a token when a faded wallet buys it
+    pub sell_inventory: bool,
+    /// How often faded wallets are reloaded and polled
+    pub interval_secs: u64,
+    /// Discovered wallets not recorded as losing again for this long are dropped
+    pub wallet_ttl_hours: u64,
+    /// Discovery fades wallets at or below this win rate ...
+    pub max_win_rate: f64,
+    /// ... that lost at least this much over the scan
+    pub min_loss_sol: f64,
+    pub max_wallets: usize,
+}
+
+impl Default for FadeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            wallets: vec![],
+            fade_blacklisted: false,
+            cooldown_secs: 1800,
+            sell_inventory: false,
+            interval_secs: 30,
+            wallet_ttl_hours: 72,
+            max_win_rate: 0.3,
+            min_loss_sol: 1.0,
+            max_wallets: 50,
+        }
+    }
+}
+
+/// Rolling evaluation of our own copies of each tracked trader. Traders whose
+/// copies fall under the thresholds are put on probation or blacklisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraderEvaluationConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// Only the last `window_copies` copies closed within `window_days` count
+    pub window_copies: usize,
+    pub window_days: u64,
+    /// Fewer closed copies than this leave a trader's status unchanged
+    pub min_copies: usize,
+    /// Probation at or below this return on the copies' cost ...
+    pub probation_return: f64,
+    /// ... or below this win rate
+    pub probation_win_rate: f64,
+    /// Blacklisted at or below this return
+    pub blacklist_return: f64,
+    /// Copy size on probation, as a share of the normal size
+    pub probation_size_fraction: f64,
+}
+
+impl Default for TraderEvaluationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 300,
+            window_copies: 20,
+            window_days: 7,
+            min_copies: 5,
+            probation_return: -0.1,
+            probation_win_rate: 0.3,
+            blacklist_return: -0.3,
+            probation_size_fraction: 0.5,
+        }
+    }
+}
+
+/// Enter a token only once several tracked traders buy it within a window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsensusConfig {
+    pub enabled: bool,
+    /// Distinct traders that must buy within the window
+    pub min_traders: usize,
+    /// Summed vote weight those traders must also reach. Set `min_traders`
+    /// to 1 to trigger on weight alone.
+    pub min_score: Option<f64>,
+    pub window_secs: u64,
+    /// Share of the agreeing traders that must sell before we exit
+    pub exit_fraction: f64,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_traders: 2,
+            min_score: None,
+            window_secs: 600,
+            exit_fraction: 0.5,
+        }
+    }
+}
+
+/// Splits a shared budget across tracked traders by how our copies of them
+/// performed over a rolling window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CopyWeightingConfig {
+    pub enabled: bool,
+    /// SOL shared by all traders' open copies
+    pub total_budget_sol: f64,
+    pub window_hours: u64,
+    /// Lowest score a losing trader is weighted at, against 1.0 for break-even
+    pub min_score: f64,
+}
+
+impl Default for CopyWeightingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            total_budget_sol: 1.0,
+            window_hours: 168,
+            min_score: 0.1,
+        }
+    }
+}
+
+/// How copied buys are sized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopySizingMode {
+    /// `copy_percentage` of the leader's buy, within our sizing policy
+    #[default]
+    Fixed,
+    /// The same share of our equity as the buy was of the leader's
+    Proportional,
+}
+
+/// Overrides for copying one trader. Unset values fall back to `[copy_trader]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraderCopyConfig {
+    pub max_sol_per_trade: Option<f64>,
+    /// Smallest copied buy; sized buys below it are raised to it
+    pub min_sol_per_trade: Option<f64>,
+    /// SOL that copies of this trader may have open at once
+    pub budget_sol: Option<f64>,
+    pub max_open_positions: Option<usize>,
+    pub max_slippage: Option<f64>,
+    /// Token types ("creator", "community") to copy; empty copies all
+    pub token_types: Vec<String>,
+    /// Wait before copying this trader's swaps
+    pub copy_delay_ms: Option<u64>,
+    /// Vote weight in consensus mode; 1.0 when unset
+    pub consensus_weight: Option<f64>,
+}
+
+/// Periodic scan of recent Heaven swaps for wallets worth copying.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// Program signatures read per scan
+    pub scan_limit: usize,
+    /// Transactions read concurrently while scanning
+    pub decode_batch_size: usize,
+    /// Best-scoring wallets written to the traders table
+    pub top_n: usize,
+    /// Closed round trips needed before a wallet is scored
+    pub min_round_trips: u64,
+    pub min_win_rate: f64,
+    pub min_realized_profit: f64,
+    /// Wallets scoring riskier than this are never written
+    pub max_risk_score: f64,
+    /// Median holds shorter than this look like bots
+    pub min_median_hold_secs: i64,
+    /// Swap rates above this look like bots
+    pub max_trades_per_hour: f64,
+    /// Share of round trips closed near break-even that marks wash trading
+    pub max_wash_share: f64,
+    /// Discovered traders that stop qualifying for this long are removed
+    pub trader_ttl_hours: u64,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 3600,
+            scan_limit: 10000,
+            decode_batch_size: 25,
+            top_n: 10,
+            min_round_trips: 3,
+            min_win_rate: 0.5,
+            min_realized_profit: 0.2,
+            max_risk_score: 0.7,
+            min_median_hold_secs: 10,
+            max_trades_per_hour: 60.0,
+            max_wash_share: 0.3,
+            trader_ttl_hours: 72,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +496,143 @@ pub struct TradingConfig {
     pub max_daily_loss_sol: f64,
     pub risk_per_trade: f64,
     pub auto_rebalance: bool,
+    #[serde(default)]
+    pub cost_basis: CostBasisMethod,
+    /// Largest share of equity held in any one token
+    #[serde(default = "default_max_token_exposure")]
+    pub max_token_exposure: f64,
+    /// Largest single buy as a share of equity
+    #[serde(default = "default_max_position_fraction")]
+    pub max_position_fraction: f64,
+    #[serde(default)]
+    pub circuit_breakers: CircuitBreakerConfig,
+    /// Targets used when `auto_rebalance` is on
+    #[serde(default)]
+    pub rebalance: RebalanceConfig,
+    #[serde(default)]
+    pub sizing: SizingConfig,
+    #[serde(default)]
+    pub execution: ExecutionConfig,
+}
+
+/// How exits are worked into thin pools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    /// Largest price impact of a single exit sell; bigger exits are split
+    pub max_exit_impact: f64,
+    /// Delay between the slices of a split exit
+    pub twap_interval_secs: u64,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            max_exit_impact: 0.05,
+            twap_interval_secs: 15,
+        }
+    }
+}
+
+/// How entry sizes are derived from equity before the liquidity cap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingPolicy {
+    /// `risk_per_trade` of equity
+    #[default]
+    FixedFraction,
+    /// Lose at most `risk_per_trade` of equity if the stop loss is hit
+    StopDistance,
+    /// Kelly fraction from the strategy's closed trades
+    Kelly,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizingConfig {
+    pub policy: SizingPolicy,
+    /// Largest price impact an entry may have on the pool
+    pub max_price_impact: f64,
+    /// Share of the full Kelly fraction to bet
+    pub kelly_multiplier: f64,
+    /// Closed trades needed before Kelly sizing replaces the fixed fraction
+    pub kelly_min_trades: u64,
+    /// Smallest share of equity Kelly sizes to, so a strategy with a negative
+    /// edge keeps trading small and its record can recover
+    #[serde(default = "default_kelly_min_fraction")]
+    pub kelly_min_fraction: f64,
+}
+
+impl Default for SizingConfig {
+    fn default() -> Self {
+        Self {
+            policy: SizingPolicy::FixedFraction,
+            max_price_impact: 0.02,
+            kelly_multiplier: 0.5,
+            kelly_min_trades: 20,
+            kelly_min_fraction: default_kelly_min_fraction(),
+        }
+    }
+}
+
+/// Target allocations between SOL and open positions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RebalanceConfig {
+    pub interval_secs: u64,
+    /// SOL kept aside for fees
+    pub min_sol_reserve: f64,
+    /// Largest share of equity in any one token
+    pub max_token_share: f64,
+    /// Largest share of equity in open positions overall
+    pub max_total_in_positions: f64,
+    /// Smaller trims are skipped
+    pub min_trade_sol: f64,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            min_sol_reserve: 0.1,
+            max_token_share: 0.3,
+            max_total_in_positions: 0.8,
+            min_trade_sol: 0.01,
+        }
+    }
+}
+
+/// Thresholds that halt new entries until trading is explicitly resumed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Failed trades in a row before halting
+    pub max_consecutive_failures: u32,
+    /// Share of failed RPC calls over the last `rpc_window` calls
+    pub max_rpc_error_rate: f64,
+    pub rpc_window: usize,
+    /// Drop in equity from its peak, as a fraction of the peak
+    pub max_drawdown: f64,
+    /// SOL balance below which trading halts
+    pub min_sol_balance: f64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: 5,
+            max_rpc_error_rate: 0.5,
+            rpc_window: 20,
+            max_drawdown: 0.3,
+            min_sol_balance: 0.05,
+        }
+    }
+}
+
+/// How sells are matched against open lots in the PnL ledger.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostBasisMethod {
+    #[default]
+    Fifo,
+    AverageCost,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +651,44 @@ pub struct MonitoringConfig {
     pub log_level: String,
 }
 
+/// Settings for the runtime that drives user-registered `Strategy` implementations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyRuntimeConfig {
+    pub tick_interval_ms: u64,
+    pub max_sol_per_intent: f64,
+    pub max_slippage: f64,
+}
+
+impl Default for StrategyRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval_ms: 1000,
+            max_sol_per_intent: 0.1,
+            max_slippage: 0.1,
+        }
+    }
+}
+
+/// Rhai rule scripts evaluated as an extra filter stage by the sniper and copy trader.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptingConfig {
+    pub enabled: bool,
+    pub scripts_dir: String,
+    pub reload_interval_secs: u64,
+    pub max_operations: u64,
+}
+
+impl Default for ScriptingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scripts_dir: "scripts".to_string(),
+            reload_interval_secs: 5,
+            max_operations: 100_000,
+        }
+    }
+}
+
 impl BotConfig {
     pub fn from_file(path: &str) -> Result<Self, BotError> {
         let config_content = std::fs::read_to_string(path)?;
@@ -129,11 +712,117 @@ impl BotConfig {
             return Err(BotError::Validation("Max concurrent trades must be greater than 0".to_string()));
         }
         
+        for (name, fraction) in [
+            ("Max token exposure", self.trading.max_token_exposure),
+            ("Max position fraction", self.trading.max_position_fraction),
+            ("Max RPC error rate", self.trading.circuit_breakers.max_rpc_error_rate),
+            ("Max drawdown", self.trading.circuit_breakers.max_drawdown),
+            ("Rebalance max token share", self.trading.rebalance.max_token_share),
+            ("Rebalance max total in positions", self.trading.rebalance.max_total_in_positions),
+            ("Max price impact", self.trading.sizing.max_price_impact),
+            ("Kelly multiplier", self.trading.sizing.kelly_multiplier),
+            ("Kelly min fraction", self.trading.sizing.kelly_min_fraction),
+            ("Max exit impact", self.trading.execution.max_exit_impact),
+            ("Discovery min win rate", self.copy_trader.discovery.min_win_rate),
+            ("Discovery max risk score", self.copy_trader.discovery.max_risk_score),
+            ("Discovery max wash share", self.copy_trader.discovery.max_wash_share),
+            ("Copy max slippage", self.copy_trader.max_slippage),
+            ("Consensus exit fraction", self.copy_trader.consensus.exit_fraction),
+            ("Probation size fraction", self.copy_trader.evaluation.probation_size_fraction),
+            ("Creator rug drawdown", self.sniper.creators.rug_drawdown),
+        ] {
+            if fraction <= 0.0 || fraction > 1.0 {
+                return Err(BotError::Validation(format!("{} must be between 0 and 1", name)));
+            }
+        }
+        
+        if self.trading.auto_rebalance && self.trading.rebalance.interval_secs == 0 {
+            return Err(BotError::Validation("Rebalance interval must be greater than 0".to_string()));
+        }
+        
+        if self.copy_trader.discovery.enabled && self.copy_trader.discovery.interval_secs == 0 {
+            return Err(BotError::Validation("Discovery interval must be greater than 0".to_string()));
+        }
+        
+        if self.copy_trader.fade.enabled && self.copy_trader.fade.interval_secs == 0 {
+            return Err(BotError::Validation("Fade interval must be greater than 0".to_string()));
+        }
+        
+        if self.copy_trader.consensus.enabled && self.copy_trader.consensus.min_traders == 0 {
+            return Err(BotError::Validation("Consensus min traders must be greater than 0".to_string()));
+        }
+        
+        if self.copy_trader.weighting.enabled && self.copy_trader.weighting.total_budget_sol <= 0.0 {
+            return Err(BotError::Validation("Copy weighting budget must be greater than 0".to_string()));
+        }
+        
+        let evaluation = &self.copy_trader.evaluation;
+        if evaluation.enabled {
+            if evaluation.interval_secs == 0 || evaluation.window_copies == 0 {
+                return Err(BotError::Validation("Trader evaluation interval and window must be greater than 0".to_string()));
+            }
+            if evaluation.blacklist_return > evaluation.probation_return {
+                return Err(BotError::Validation("Trader blacklist return must not exceed the probation return".to_string()));
+            }
+            if !(0.0..=1.0).contains(&evaluation.probation_win_rate) {
+                return Err(BotError::Validation("Trader probation win rate must be between 0 and 1".to_string()));
+            }
+        }
+        
         // Validate sniper config
         if self.sniper.enabled && self.sniper.max_sol_per_trade <= 0.0 {
             return Err(BotError::Validation("Max SOL per trade must be greater than 0".to_string()));
         }
         
+        if self.sniper.queue.workers == 0 || self.sniper.queue.capacity == 0 {
+            return Err(BotError::Validation("Launch queue workers and capacity must be greater than 0".to_string()));
+        }
+        
+        let creators = &self.sniper.creators;
+        if creators.enabled {
+            if creators.interval_secs == 0 || creators.watch_hours == 0 {
+                return Err(BotError::Validation("Creator tracking interval and watch hours must be greater than 0".to_string()));
+            }
+            if creators.blacklist_rugs == 0 {
+                return Err(BotError::Validation("Creator blacklist rugs must be greater than 0".to_string()));
+            }
+            for (name, score) in [
+                ("Creator unknown score", Some(creators.unknown_score)),
+                ("Creator min score", creators.min_score),
+                ("Creator verified min score", Some(creators.verified_min_score)),
+            ] {
+                if score.is_some_and(|score| !(0.0..=1.0).contains(&score)) {
+                    return Err(BotError::Validation(format!("{} must be between 0 and 1", name)));
+                }
+            }
+        }
+        
+        // Validate sniper strategy definitions
+        for strategy in &self.sniper.strategies {
+            if strategy.size_multiplier <= 0.0 {
+                return Err(BotError::Validation(format!("Strategy '{}' size multiplier must be greater than 0", strategy.name)));
+            }
+            strategy.exit.validate(&strategy.name)?;
+        }
+        crate::strategy_registry::StrategyRegistry::from_config(&self.sniper)?;
+        
+        // Validate copy trader exit rules
+        self.copy_trader.exit.validate("copy_trader")?;
+        
+        // Validate per-trader caps and floors
+        for (address, trader) in &self.copy_trader.traders {
+            if let (Some(min), Some(max)) = (trader.min_sol_per_trade, trader.max_sol_per_trade) {
+                if min > max {
+                    return Err(BotError::Validation(format!("Trader {} min SOL per trade exceeds its max", address)));
+                }
+            }
+            if let Some(slippage) = trader.max_slippage {
+                if slippage <= 0.0 || slippage > 1.0 {
+                    return Err(BotError::Validation(format!("Trader {} max slippage must be between 0 and 1", address)));
+                }
+            }
+        }
+        
         Ok(())
     }
 }
@@ -172,6 +861,11 @@ impl Default for BotConfig {
                 max_market_cap: 1_000_000.0,
                 volume_threshold: 1000.0,
                 launch_detection_delay_ms: 100,
+                verified_creators: vec![],
+                creators: CreatorReputationConfig::default(),
+                queue: LaunchQueueConfig::default(),
+                watchlist: vec![],
+                strategies: vec![],
             },
             copy_trader: CopyTraderConfig {
                 enabled: true,
@@ -184,6 +878,16 @@ impl Default for BotConfig {
                 whitelisted_traders: vec![],
                 delay_ms: 500,
                 auto_approve: false,
+                max_slippage: 0.1,
+                realtime: true,
+                exit: ExitRules::default(),
+                discovery: DiscoveryConfig::default(),
+                sizing_mode: CopySizingMode::Fixed,
+                traders: HashMap::new(),
+                weighting: CopyWeightingConfig::default(),
+                consensus: ConsensusConfig::default(),
+                fade: FadeConfig::default(),
+                evaluation: TraderEvaluationConfig::default(),
             },
             bundler: BundlerConfig {
                 enabled: true,
@@ -202,7 +906,14 @@ impl Default for BotConfig {
                 max_daily_trades: 100,
                 max_daily_loss_sol: 1.0,
                 risk_per_trade: 0.02,
-                auto_rebalance: true,
+                auto_rebalance: false,
+                cost_basis: CostBasisMethod::Fifo,
+                max_token_exposure: default_max_token_exposure(),
+                max_position_fraction: default_max_position_fraction(),
+                circuit_breakers: CircuitBreakerConfig::default(),
+                rebalance: RebalanceConfig::default(),
+                sizing: SizingConfig::default(),
+                execution: ExecutionConfig::default(),
             },
             database: DatabaseConfig {
                 url: "sqlite:trading_bot.db".to_string(),
@@ -216,6 +927,8 @@ impl Default for BotConfig {
                 alert_webhook: None,
                 log_level: "info".to_string(),
             },
+            strategy_runtime: StrategyRuntimeConfig::default(),
+            scripting: ScriptingConfig::default(),
         }
     }
 }
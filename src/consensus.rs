@@ -0,0 +1,125 @@
+use crate::config::ConsensusConfig;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Turns tracked traders' swaps into consensus signals: a token is entered
+/// once enough traders buy it within the window, and exited once enough of
+/// those traders have sold. Consensus needs `min_traders` distinct buyers
+/// and, when `min_score` is set, their summed weight to reach it as well.
+/// Entries are kept in memory only, so after a restart open consensus
+/// positions exit on their exit rules alone.
+#[derive(Debug)]
+pub struct ConsensusTracker {
+    config: ConsensusConfig,
+    votes: HashMap<String, Vec<ConsensusVote>>,
+    entries: HashMap<String, ConsensusEntry>,
+}
+
+/// A tracked trader's buy counting towards consensus on a token.
+#[derive(Debug, Clone)]
+pub struct ConsensusVote {
+    pub trader_address: String,
+    pub weight: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// Our open entry in a token and the traders whose buys triggered it.
+#[derive(Debug, Clone)]
+pub struct ConsensusEntry {
+    pub copy_trade_id: String,
+    pub traders: Vec<String>,
+    pub exited: Vec<String>,
+    pub exit_due: bool,
+}
+
+impl ConsensusTracker {
+    pub fn new(config: ConsensusConfig) -> Self {
+        Self {
+            config,
+            votes: HashMap::new(),
+            entries: HashMap::new(),
+        }
+    }
+    
+    /// Count a buy. Returns the agreeing traders when the token reaches consensus.
+    pub fn record_buy(
+        &mut self,
+        token_mint: &str,
+        trader_address: &str,
+        weight: f64,
+        at: DateTime<Utc>,
+    ) -> Option<Vec<String>> {
+        if self.entries.contains_key(token_mint) {
+            return None;
+        }
+        
+        // A trader adding to their buy still counts once
+        let cutoff = at - Duration::seconds(self.config.window_secs as i64);
+        let votes = self.votes.entry(token_mint.to_string()).or_default();
+        votes.retain(|v| v.at >= cutoff && v.trader_address != trader_address);
+        votes.push(ConsensusVote {
+            trader_address: trader_address.to_string(),
+            weight,
+            at,
+        });
+        
+        let score: f64 = votes.iter().map(|v| v.weight).sum();
+        let reached = votes.len() >= self.config.min_traders
+            && self.config.min_score.map_or(true, |min_score| score >= min_score);
+        if !reached {
+            return None;
+        }
+        
+        let traders = votes.iter().map(|v| v.trader_address.clone()).collect();
+        self.votes.remove(token_mint);
+        Some(traders)
+    }
+    
+    pub fn record_entry(&mut self, token_mint: &str, copy_trade_id: &str, traders: Vec<String>) {
+        self.entries.insert(token_mint.to_string(), ConsensusEntry {
+            copy_trade_id: copy_trade_id.to_string(),
+            traders,
+            exited: Vec::new(),
+            exit_due: false,
+        });
+    }
+    
+    /// Count a sell. Returns the copy trade to exit once `exit_fraction` of
+    /// the traders behind the entry have sold.
+    pub fn record_sell(&mut self, token_mint: &str, trader_address: &str) -> Option<String> {
+        let entry = self.entries.get_mut(token_mint)?;
+        if entry.exit_due
+            || !entry.traders.iter().any(|t| t == trader_address)
+            || entry.exited.iter().any(|t| t == trader_address)
+        {
+            return None;
+        }
+        
+        entry.exited.push(trader_address.to_string());
+        let exited_share = entry.exited.len() as f64 / entry.traders.len() as f64;
+        if exited_share < self.config.exit_fraction {
+            return None;
+        }
+        
+        entry.exit_due = true;
+        Some(entry.copy_trade_id.clone())
+    }
+    
+    pub fn exit_due(&self, copy_trade_id: &str) -> bool {
+        self.entries.values().any(|e| e.copy_trade_id == copy_trade_id && e.exit_due)
+    }
+    
+    /// Drop the entry of a closed copy trade so the token can be entered again.
+    pub fn forget(&mut self, copy_trade_id: &str) {
+        self.entries.retain(|_, e| e.copy_trade_id != copy_trade_id);
+    }
+    
+    /// Drop votes that fell out of the window.
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::seconds(self.config.window_secs as i64);
+        self.votes.retain(|_, votes| {
+            votes.retain(|v| v.at >= cutoff);
+            !votes.is_empty()
+        });
+    }
+}
@@ -1,10 +1,23 @@
 use crate::{
-    config::BotConfig,
+    config::{BotConfig, CopySizingMode, TraderCopyConfig},
+    consensus::ConsensusTracker,
     error::BotError,
     heaven_client::HeavenClient,
     database::Database,
+    discovery::TraderDiscovery,
+    evaluation::{next_status, score_copies, CopyScore},
+    fade::FadeFeed,
     monitoring::Metrics,
-    types::{Trade, Trader, CopyTrade},
+    exits::{ExitPlan, ExitReason, ExitSignal, ExitSlice, ExitSlicer},
+    positions::{PositionManager, new_position},
+    risk::RiskEngine,
+    sizing::{PositionSizer, SizingRequest},
+    trader_stream::{LeaderSwap, StreamSettings, TraderStream, TraderSubscription},
+    scripting::{ScriptEngine, ScriptDecision},
+    types::{
+        Trade, Trader, CopyTrade, CopyExecution, SniperStrategy, TraderStatus, TraderStatusChange,
+        EventType, EventSeverity,
+    },
 };
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -13,10 +26,16 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tracing::{info, warn, error, debug};
 use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+
+// Leader trades this far behind the newest one seen are still deduplicated one by one
+const SEEN_TRADE_WINDOW_SECS: i64 = 6 * 3600;
+
+// Leader sells of at least this share of their bag are mirrored as full exits
+const FULL_EXIT_FRACTION: f64 = 0.98;
 
 pub struct CopyTraderBot {
     config: BotConfig,
@@ -24,11 +43,36 @@ pub struct CopyTraderBot {
     heaven_client: Arc<HeavenClient>,
     database: Arc<Database>,
     metrics: Arc<Metrics>,
+    positions: Arc<PositionManager>,
+    risk: Arc<RiskEngine>,
+    sizer: Arc<PositionSizer>,
+    fade: Arc<FadeFeed>,
     wallet: Arc<Keypair>,
     is_running: Arc<RwLock<bool>>,
     tracked_traders: Arc<RwLock<HashMap<String, Trader>>>,
     active_copy_trades: Arc<RwLock<HashMap<String, CopyTrade>>>,
     trader_performance: Arc<RwLock<HashMap<String, TraderPerformance>>>,
+    // Latest status change of each trader that has one; others are active
+    trader_statuses: RwLock<HashMap<String, TraderStatusChange>>,
+    script_engine: Option<Arc<ScriptEngine>>,
+    exit_slicer: RwLock<ExitSlicer>,
+    swap_receiver: Mutex<mpsc::UnboundedReceiver<LeaderSwap>>,
+    // Copies held back by a trader's copy_delay_ms come back here once due
+    delayed_sender: mpsc::UnboundedSender<DelayedCopy>,
+    delayed_receiver: Mutex<mpsc::UnboundedReceiver<DelayedCopy>>,
+    // Budget weights of tracked traders, recomputed once per scan
+    trader_weights: RwLock<HashMap<String, f64>>,
+    trader_stream: Arc<TraderStream>,
+    subscriptions: RwLock<HashMap<String, TraderSubscription>>,
+    discovery: Option<Arc<TraderDiscovery>>,
+    consensus: RwLock<ConsensusTracker>,
+}
+
+/// A leader trade waiting out its trader's copy delay.
+struct DelayedCopy {
+    trader: Trader,
+    trade: Trade,
+    detected_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,19 +92,70 @@ impl CopyTraderBot {
         heaven_client: Arc<HeavenClient>,
         database: Arc<Database>,
         metrics: Arc<Metrics>,
+        positions: Arc<PositionManager>,
+        risk: Arc<RiskEngine>,
+        sizer: Arc<PositionSizer>,
+        fade: Arc<FadeFeed>,
         wallet: Arc<Keypair>,
     ) -> Result<Self, BotError> {
+        // Initialize rule scripts
+        let script_engine = if config.scripting.enabled {
+            Some(Arc::new(ScriptEngine::new(&config.scripting)?))
+        } else {
+            None
+        };
+        
+        let exit_slicer = RwLock::new(ExitSlicer::new(config.trading.execution.twap_interval_secs));
+        let (swap_sender, swap_receiver) = mpsc::unbounded_channel();
+        let (delayed_sender, delayed_receiver) = mpsc::unbounded_channel();
+        let trader_stream = Arc::new(TraderStream::new(
+            StreamSettings {
+                ws_url: config.solana.ws_url.clone(),
+                program_id: config.heaven.program_id.clone(),
+                reconnect_delay: std::time::Duration::from_millis(config.solana.retry_delay_ms),
+            },
+            heaven_client.clone(),
+            swap_sender,
+        ));
+        
+        let discovery = if config.copy_trader.discovery.enabled {
+            Some(Arc::new(TraderDiscovery::new(
+                config.clone(),
+                heaven_client.clone(),
+                database.clone(),
+            )))
+        } else {
+            None
+        };
+        
+        let consensus = RwLock::new(ConsensusTracker::new(config.copy_trader.consensus.clone()));
+        
         Ok(Self {
             config,
             rpc_client,
             heaven_client,
             database,
             metrics,
+            positions,
+            risk,
+            sizer,
+            fade,
             wallet,
             is_running: Arc::new(RwLock::new(false)),
             tracked_traders: Arc::new(RwLock::new(HashMap::new())),
             active_copy_trades: Arc::new(RwLock::new(HashMap::new())),
             trader_performance: Arc::new(RwLock::new(HashMap::new())),
+            trader_statuses: RwLock::new(HashMap::new()),
+            script_engine,
+            exit_slicer,
+            swap_receiver: Mutex::new(swap_receiver),
+            delayed_sender,
+            delayed_receiver: Mutex::new(delayed_receiver),
+            trader_weights: RwLock::new(HashMap::new()),
+            trader_stream,
+            subscriptions: RwLock::new(HashMap::new()),
+            discovery,
+            consensus,
         })
     }
     
@@ -68,9 +163,29 @@ impl CopyTraderBot {
         info!("Starting Copy Trading Bot...");
         *self.is_running.write().await = true;
         
+        // Blacklisted traders stay untracked across restarts
+        let statuses = self.database.get_latest_trader_statuses().await?;
+        self.trader_statuses.write().await.extend(
+            statuses.into_iter().map(|change| (change.trader_address.clone(), change))
+        );
+        
         // Initialize tracked traders
         self.initialize_tracked_traders().await?;
         
+        // Resume copy trades that were open before a restart
+        self.restore_active_copy_trades().await?;
+        
+        // Discovered traders land in the database and are loaded from there
+        if let Some(discovery) = &self.discovery {
+            let discovery = discovery.clone();
+            let is_running = self.is_running.clone();
+            tokio::spawn(async move {
+                if let Err(e) = discovery.run(is_running).await {
+                    error!("Trader discovery error: {}", e);
+                }
+            });
+        }
+        
         // Start the main copy trading loop
         self.main_copy_trading_loop().await?;
         
@@ -80,6 +195,10 @@ impl CopyTraderBot {
     pub async fn stop(&mut self) -> Result<(), BotError> {
         info!("Stopping Copy Trading Bot...");
         *self.is_running.write().await = false;
+        
+        for (_, subscription) in self.subscriptions.write().await.drain() {
+            subscription.close();
+        }
         Ok(())
     }
     
@@ -88,13 +207,64 @@ impl CopyTraderBot {
             std::time::Duration::from_millis(self.config.copy_trader.delay_ms)
         );
         
+        let mut swaps = self.swap_receiver.lock().await;
+        let mut delayed = self.delayed_receiver.lock().await;
+        let mut last_trader_reload = tokio::time::Instant::now();
+        let mut last_evaluation = tokio::time::Instant::now();
+        
         while *self.is_running.read().await {
-            interval.tick().await;
+            // Streamed swaps are copied the moment they arrive
+            tokio::select! {
+                Some(swap) = swaps.recv() => {
+                    let mut batch = vec![swap];
+                    while let Ok(swap) = swaps.try_recv() {
+                        batch.push(swap);
+                    }
+                    self.copy_leader_swaps(batch).await;
+                    continue;
+                }
+                Some(copy) = delayed.recv() => {
+                    self.copy_trade(&copy.trader, copy.trade, copy.detected_at).await;
+                    continue;
+                }
+                _ = interval.tick() => {}
+            }
             
-            // Scan for new trades from tracked traders
-            if let Err(e) = self.scan_trader_activity().await {
-                warn!("Failed to scan trader activity: {}", e);
-                continue;
+            // Pick up edited rule scripts
+            if let Some(engine) = &self.script_engine {
+                if let Err(e) = engine.reload_if_changed().await {
+                    warn!("Failed to reload scripts: {}", e);
+                }
+            }
+            
+            // Pick up traders written by discovery
+            if self.discovery.is_some()
+                && last_trader_reload.elapsed().as_secs() >= self.config.copy_trader.discovery.interval_secs
+            {
+                last_trader_reload = tokio::time::Instant::now();
+                if let Err(e) = self.initialize_tracked_traders().await {
+                    warn!("Failed to reload tracked traders: {}", e);
+                }
+            }
+            
+            // Keep one subscription per tracked trader
+            if self.config.copy_trader.realtime {
+                self.sync_subscriptions().await;
+            }
+            
+            // Weigh traders once per scan rather than on every buy
+            if self.config.copy_trader.weighting.enabled {
+                if let Err(e) = self.refresh_trader_weights().await {
+                    warn!("Failed to weigh traders: {}", e);
+                }
+            }
+            
+            // Poll tracked traders; with subscriptions this only backfills missed swaps
+            self.scan_trader_activity().await;
+            
+            // Forget consensus votes that fell out of the window
+            if self.config.copy_trader.consensus.enabled {
+                self.consensus.write().await.prune(Utc::now());
             }
             
             // Process active copy trades
@@ -107,6 +277,15 @@ impl CopyTraderBot {
                 warn!("Failed to update trader performance: {}", e);
             }
             
+            // Demote traders whose copies keep losing
+            let evaluation = &self.config.copy_trader.evaluation;
+            if evaluation.enabled && last_evaluation.elapsed().as_secs() >= evaluation.interval_secs {
+                last_evaluation = tokio::time::Instant::now();
+                if let Err(e) = self.evaluate_traders().await {
+                    warn!("Failed to evaluate traders: {}", e);
+                }
+            }
+            
             // Update copy trading metrics
             self.update_copy_trading_metrics().await;
         }
@@ -120,12 +299,25 @@ impl CopyTraderBot {
         // Load traders from database
         let traders = self.database.get_tracked_traders().await?;
         
+        // Traders removed from the database, e.g. expired by discovery, are dropped
+        self.tracked_traders.write().await.retain(|address, _| {
+            traders.iter().any(|t| &t.address == address)
+        });
+        
         for trader in traders {
             if self.should_track_trader(&trader).await {
-                self.tracked_traders.write().await.insert(
-                    trader.address.clone(),
-                    trader.clone(),
-                );
+                let mut tracked_traders = self.tracked_traders.write().await;
+                
+                // Refresh stats of traders already followed
+                if let Some(tracked) = tracked_traders.get_mut(&trader.address) {
+                    *tracked = trader;
+                    continue;
+                }
+                if tracked_traders.len() >= self.config.copy_trader.max_traders {
+                    break;
+                }
+                tracked_traders.insert(trader.address.clone(), trader.clone());
+                drop(tracked_traders);
                 
                 // Initialize performance tracking
                 self.trader_performance.write().await.insert(
@@ -147,6 +339,34 @@ impl CopyTraderBot {
         Ok(())
     }
     
+    async fn restore_active_copy_trades(&self) -> Result<(), BotError> {
+        let positions = self.positions.open_positions_by_source("copy_trader").await;
+        let mut active_trades = self.active_copy_trades.write().await;
+        let exit_plan = ExitPlan::new(&self.config.copy_trader.exit, &self.config.trading);
+        
+        for position in &positions {
+            let (stop_loss, take_profit) = exit_plan.levels(position);
+            if let Err(e) = self.positions.set_exit_levels(&position.id, stop_loss, take_profit).await {
+                warn!("Failed to restore exit levels of {}: {}", position.id, e);
+            }
+            
+            // Copy positions share their id with the copy trade that opened them
+            match self.database.get_copy_trade(&position.id).await? {
+                Some(mut copy_trade) => {
+                    copy_trade.token_amount = position.token_amount;
+                    active_trades.insert(copy_trade.id.clone(), copy_trade);
+                }
+                None => warn!("No copy trade found for open position {}", position.id),
+            }
+        }
+        
+        if !active_trades.is_empty() {
+            info!("Restored {} open copy trades", active_trades.len());
+        }
+        
+        Ok(())
+    }
+    
     async fn should_track_trader(&self, trader: &Trader) -> bool {
         // Check blacklist/whitelist
         if !self.config.copy_trader.whitelisted_traders.is_empty() {
@@ -155,7 +375,9 @@ impl CopyTraderBot {
             }
         }
         
-        if self.config.copy_trader.blacklisted_traders.contains(&trader.address) {
+        if self.config.copy_trader.blacklisted_traders.contains(&trader.address)
+            || self.trader_status(&trader.address).await == TraderStatus::Blacklisted
+        {
             return false;
         }
         
@@ -165,71 +387,341 @@ impl CopyTraderBot {
         trader.total_volume >= self.config.copy_trader.min_trader_balance // Minimum volume
     }
     
-    async fn scan_trader_activity(&self) -> Result<(), BotError> {
-        let tracked_traders = self.tracked_traders.read().await;
+    async fn scan_trader_activity(&self) {
+        let traders: Vec<Trader> = self.tracked_traders.read().await.values().cloned().collect();
         
-        for (address, trader) in tracked_traders.iter() {
-            // Get recent trades from this trader
-            let recent_trades = self.heaven_client.get_trader_trades(address).await?;
-            
-            for trade in recent_trades {
-                // Check if this is a new trade we haven't seen
-                if self.is_new_trade(&trade).await {
-                    debug!("New trade detected from trader {}: {}", trader.name, trade.token_mint);
-                    
-                    // Evaluate if we should copy this trade
-                    if self.should_copy_trade(&trade, trader).await {
-                        info!("Copying trade from {}: {} {}", trader.name, trade.trade_type, trade.token_mint);
-                        
-                        if let Err(e) = self.execute_copy_trade(&trade, trader).await {
-                            error!("Failed to copy trade: {}", e);
-                        }
+        // Traders are polled concurrently and one failing does not hold up the rest
+        futures::future::join_all(traders.iter().map(|trader| async move {
+            let result = match self.heaven_client.get_trader_trades(&trader.address).await {
+                Ok(trades) => self.copy_new_trades(trader, trades, &HashMap::new()).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                warn!("Failed to scan trader {}: {}", trader.name, e);
+            }
+        })).await;
+    }
+    
+    async fn copy_leader_swaps(&self, swaps: Vec<LeaderSwap>) {
+        let mut by_trader: HashMap<String, Vec<Trade>> = HashMap::new();
+        let mut detected: HashMap<String, DateTime<Utc>> = HashMap::new();
+        for swap in swaps {
+            detected.insert(swap.trade.id.clone(), swap.detected_at);
+            by_trader.entry(swap.trader_address).or_default().push(swap.trade);
+        }
+        let detected = &detected;
+        
+        let tracked_traders = self.tracked_traders.read().await.clone();
+        
+        // Each trader's swaps stay in order; different traders are copied concurrently
+        futures::future::join_all(by_trader.into_iter().map(|(address, trades)| {
+            let trader = tracked_traders.get(&address).cloned();
+            async move {
+                let Some(trader) = trader else {
+                    if self.fade.is_faded(&address).await {
+                        self.fade.record_trades(&address, &trades).await;
+                    } else {
+                        debug!("Ignoring swaps from untracked wallet {}", address);
                     }
+                    return;
+                };
+                if let Err(e) = self.copy_new_trades(&trader, trades, detected).await {
+                    warn!("Failed to copy streamed swaps from {}: {}", trader.name, e);
+                }
+            }
+        })).await;
+    }
+    
+    /// Copy the trades not seen before. `detected` holds when streamed swaps
+    /// arrived; polled trades count as detected when they are claimed.
+    async fn copy_new_trades(
+        &self,
+        trader: &Trader,
+        trades: Vec<Trade>,
+        detected: &HashMap<String, DateTime<Utc>>,
+    ) -> Result<(), BotError> {
+        let claimed_at = Utc::now();
+        for trade in self.claim_new_trades(&trader.address, trades).await? {
+            debug!("New trade detected from trader {}: {}", trader.name, trade.token_mint);
+            let detected_at = detected.get(&trade.id).copied().unwrap_or(claimed_at);
+            
+            // Delayed copies wait in their own task so the loop keeps running
+            if let Some(delay_ms) = self.trader_config(&trader.address).and_then(|t| t.copy_delay_ms) {
+                let copy = DelayedCopy { trader: trader.clone(), trade, detected_at };
+                let sender = self.delayed_sender.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                    let _ = sender.send(copy);
+                });
+                continue;
+            }
+            
+            self.copy_trade(trader, trade, detected_at).await;
+        }
+        
+        Ok(())
+    }
+    
+    async fn copy_trade(&self, trader: &Trader, trade: Trade, detected_at: DateTime<Utc>) {
+        // In consensus mode trades are votes rather than signals of their own
+        if self.config.copy_trader.consensus.enabled {
+            if let Err(e) = self.vote_consensus(&trade, trader, detected_at).await {
+                error!("Failed to copy consensus trade: {}", e);
+            }
+            return;
+        }
+        
+        // Evaluate if we should copy this trade
+        if self.should_copy_trade(&trade, trader).await {
+            info!("Copying trade from {}: {} {}", trader.name, trade.trade_type, trade.token_mint);
+            
+            if let Err(e) = self.execute_copy_trade(&trade, trader, detected_at, false).await {
+                error!("Failed to copy trade: {}", e);
+            }
+        }
+    }
+    
+    /// Buys enter a token once enough traders agree; sells flag our entry for
+    /// exit once enough of those traders have left.
+    async fn vote_consensus(&self, trade: &Trade, trader: &Trader, detected_at: DateTime<Utc>) -> Result<(), BotError> {
+        match trade.trade_type.as_str() {
+            "buy" => {
+                let weight = self.trader_config(&trader.address)
+                    .and_then(|t| t.consensus_weight)
+                    .unwrap_or(1.0);
+                let Some(traders) = self.consensus.write().await
+                    .record_buy(&trade.token_mint, &trader.address, weight, trade.timestamp)
+                else {
+                    debug!("{} voted for {}", trader.name, trade.token_mint);
+                    return Ok(());
+                };
+                
+                info!("Consensus on {} from {} traders", trade.token_mint, traders.len());
+                if !self.should_copy_trade(trade, trader).await {
+                    return Ok(());
+                }
+                if let Some(copy_trade) = self.execute_copy_trade(trade, trader, detected_at, true).await? {
+                    self.consensus.write().await.record_entry(&trade.token_mint, &copy_trade.id, traders);
                 }
             }
+            "sell" => {
+                if let Some(copy_trade_id) = self.consensus.write().await.record_sell(&trade.token_mint, &trader.address) {
+                    info!("Consensus traders are leaving {}, exiting copy trade {}", trade.token_mint, copy_trade_id);
+                }
+            }
+            _ => {}
         }
         
         Ok(())
     }
     
-    async fn is_new_trade(&self, trade: &Trade) -> bool {
-        // Check if we've already seen this trade
-        let active_trades = self.active_copy_trades.read().await;
-        !active_trades.contains_key(&trade.id)
+    async fn sync_subscriptions(&self) {
+        let mut tracked: Vec<String> = self.tracked_traders.read().await.keys().cloned().collect();
+        tracked.extend(self.fade.wallets().await);
+        let mut subscriptions = self.subscriptions.write().await;
+        
+        let stale: Vec<String> = subscriptions.iter()
+            .filter(|(address, subscription)| !tracked.contains(address) || subscription.is_finished())
+            .map(|(address, _)| address.clone())
+            .collect();
+        for address in stale {
+            if let Some(subscription) = subscriptions.remove(&address) {
+                subscription.close();
+            }
+        }
+        
+        for address in tracked {
+            if subscriptions.contains_key(&address) {
+                continue;
+            }
+            
+            // Without a watermark the first streamed swap would only set one
+            if let Err(e) = self.seed_watermark(&address).await {
+                warn!("Failed to seed watermark for {}: {}", address, e);
+                continue;
+            }
+            let subscription = self.trader_stream.subscribe(address.clone());
+            subscriptions.insert(address, subscription);
+        }
     }
     
-    async fn should_copy_trade(&self, trade: &Trade, trader: &Trader) -> bool {
-        // Check if we're at max traders limit
-        let active_trades = self.active_copy_trades.read().await;
-        if active_trades.len() >= self.config.copy_trader.max_traders {
-            return false;
+    /// Start a wallet's watermark at now if it has none, so swaps from here on are new.
+    async fn seed_watermark(&self, address: &str) -> Result<(), BotError> {
+        if self.database.get_trader_watermark(address).await?.is_none() {
+            self.database.advance_trader_watermark(address, Utc::now()).await?;
+            info!("Following {} from now", address);
+        }
+        Ok(())
+    }
+    
+    /// Keep only leader trades never seen before, claiming them in the database
+    /// so they are not copied again after a restart. Trades are claimed before
+    /// they are copied, so a crash mid-copy skips a trade rather than doubling it.
+    async fn claim_new_trades(&self, trader_address: &str, mut trades: Vec<Trade>) -> Result<Vec<Trade>, BotError> {
+        let Some(latest) = trades.iter().map(|t| t.timestamp).max() else {
+            return Ok(Vec::new());
+        };
+        trades.sort_by_key(|t| t.timestamp);
+        
+        let watermark = self.database.get_trader_watermark(trader_address).await?;
+        let mut new_trades = Vec::new();
+        for trade in trades {
+            let signature = trade.transaction_signature.clone().unwrap_or_else(|| trade.id.clone());
+            
+            // History can come back reordered, so recent trades are checked one by one
+            if let Some(watermark) = watermark {
+                if trade.timestamp < watermark - Duration::seconds(SEEN_TRADE_WINDOW_SECS) {
+                    continue;
+                }
+            }
+            
+            if self.database.mark_trader_trade_seen(trader_address, &signature, trade.timestamp).await? {
+                new_trades.push(trade);
+            }
         }
         
-        // Check if we have sufficient balance
-        let balance = self.heaven_client.get_sol_balance().await.unwrap_or(0.0);
-        let copy_amount = trade.amount_sol * self.config.copy_trader.copy_percentage;
+        self.database.advance_trader_watermark(trader_address, latest).await?;
+        let high_water = watermark.map_or(latest, |w| w.max(latest));
+        self.database.prune_seen_trader_trades(
+            trader_address,
+            high_water - Duration::seconds(SEEN_TRADE_WINDOW_SECS),
+        ).await?;
         
-        if balance < copy_amount {
-            return false;
+        // A trader seen for the first time only sets the starting point; their history is not copied
+        if watermark.is_none() {
+            info!("Starting to follow {} from {} after {} existing trades", trader_address, latest, new_trades.len());
+            return Ok(Vec::new());
         }
         
-        // Check if this trade type is allowed
-        match trade.trade_type.as_str() {
-            "buy" => true, // Always allow buys
+        Ok(new_trades)
+    }
+    
+    async fn should_copy_trade(&self, trade: &Trade, trader: &Trader) -> bool {
+        // Buys need room for another copy and the SOL they will actually spend;
+        // sells only ever exit, so they are mirrored whatever our balance
+        let copy_amount = match trade.trade_type.as_str() {
+            "buy" => {
+                if self.active_copy_trades.read().await.len() >= self.config.copy_trader.max_traders {
+                    return false;
+                }
+                if self.fade.in_cooldown(&trade.token_mint).await
+                    || !self.token_type_allowed(trader, &trade.token_mint).await
+                {
+                    return false;
+                }
+                
+                let copy_amount = match self.copy_buy_amount(trade, trader).await {
+                    Ok(amount) => amount,
+                    Err(e) => {
+                        warn!("Failed to size copy of {} {}: {}", trader.name, trade.token_mint, e);
+                        return false;
+                    }
+                };
+                let balance = self.heaven_client.get_sol_balance().await.unwrap_or(0.0);
+                if balance < copy_amount {
+                    return false;
+                }
+                copy_amount
+            }
             "sell" => {
-                // Only copy sells if we have the token
-                self.heaven_client.get_token_balance(&trade.token_mint).await.unwrap_or(0.0) > 0.0
+                // Only copy sells if we hold a position copied from this trader
+                let held = self.leader_position_tokens(&trader.address, &trade.token_mint).await;
+                if held <= 0.0 {
+                    return false;
+                }
+                let fraction = self.leader_sell_fraction(trade, trader).unwrap_or(0.0);
+                let tokens = if fraction >= FULL_EXIT_FRACTION { held } else { held * fraction };
+                tokens * trade.price
+            }
+            _ => return false,
+        };
+        
+        // Rule scripts can veto the copy
+        self.scripts_allow_copy(trade, trader, copy_amount).await
+    }
+    
+    fn trader_config(&self, trader_address: &str) -> Option<&TraderCopyConfig> {
+        self.config.copy_trader.traders.get(trader_address)
+    }
+    
+    // Types are only known for launches we have recorded
+    async fn token_type_allowed(&self, trader: &Trader, token_mint: &str) -> bool {
+        let Some(token_types) = self.trader_config(&trader.address)
+            .map(|t| &t.token_types)
+            .filter(|types| !types.is_empty())
+        else {
+            return true;
+        };
+        
+        match self.database.get_token_launch(token_mint).await {
+            Ok(Some(launch)) => token_types.contains(&launch.token_type),
+            Ok(None) => {
+                debug!("Not copying {} into {}: token type unknown", trader.name, token_mint);
+                false
+            }
+            Err(e) => {
+                warn!("Failed to look up token type of {}: {}", token_mint, e);
+                false
             }
-            _ => false,
         }
     }
     
-    async fn execute_copy_trade(&self, original_trade: &Trade, trader: &Trader) -> Result<(), BotError> {
-        // Calculate copy trade amount
-        let copy_amount = original_trade.amount_sol * self.config.copy_trader.copy_percentage;
+    async fn scripts_allow_copy(&self, trade: &Trade, trader: &Trader, copy_amount: f64) -> bool {
+        let Some(engine) = &self.script_engine else {
+            return true;
+        };
+        
+        let market_data = match self.heaven_client.get_market_data(&trade.token_mint).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("No market data for scripted copy check on {}: {}", trade.token_mint, e);
+                return true;
+            }
+        };
+        
+        match engine.evaluate_copy(&market_data, &trader.address, &trade.trade_type, copy_amount).await {
+            ScriptDecision::Reject => {
+                debug!("Copy of {} {} rejected by rule scripts", trader.name, trade.token_mint);
+                false
+            }
+            _ => true,
+        }
+    }
+    
+    /// Copy a leader's trade. Returns the copy trade when a buy opened a position.
+    /// Consensus entries are triggered by several traders, so their positions
+    /// are attributed to none of them and do not draw on the triggering
+    /// trader's allocation.
+    async fn execute_copy_trade(
+        &self,
+        original_trade: &Trade,
+        trader: &Trader,
+        detected_at: DateTime<Utc>,
+        consensus: bool,
+    ) -> Result<Option<CopyTrade>, BotError> {
+        // Buys are sized from our own bankroll and the pool; sells mirror the
+        // share of their bag the leader sold
+        let (copy_amount, copy_tokens) = if original_trade.trade_type == "buy" {
+            let amount = self.copy_buy_amount(original_trade, trader).await?;
+            let amount = if consensus { amount } else { self.fit_trader_allocation(trader, amount).await? };
+            if amount <= 0.0 {
+                debug!("Sizing left nothing to copy in {}", original_trade.token_mint);
+                return Ok(None);
+            }
+            (amount, 0.0)
+        } else {
+            let held = self.leader_position_tokens(&trader.address, &original_trade.token_mint).await;
+            let fraction = self.leader_sell_fraction(original_trade, trader)?;
+            let tokens = if fraction >= FULL_EXIT_FRACTION { held } else { held * fraction };
+            if tokens <= 0.0 {
+                debug!("No copied position in {} to sell", original_trade.token_mint);
+                return Ok(None);
+            }
+            (tokens * original_trade.price, tokens)
+        };
         
         // Create copy trade
-        let copy_trade = CopyTrade {
+        let mut copy_trade = CopyTrade {
             id: uuid::Uuid::new_v4().to_string(),
             original_trade_id: original_trade.id.clone(),
             trader_address: trader.address.clone(),
@@ -237,39 +729,249 @@ impl CopyTraderBot {
             token_mint: original_trade.token_mint.clone(),
             trade_type: original_trade.trade_type.clone(),
             amount_sol: copy_amount,
-            token_amount: 0.0, // Will be calculated
+            token_amount: copy_tokens, // Filled in for buys once settled
             price: original_trade.price,
-            slippage: self.config.copy_trader.max_slippage,
+            slippage: self.trader_config(&trader.address)
+                .and_then(|t| t.max_slippage)
+                .unwrap_or(self.config.copy_trader.max_slippage),
             timestamp: Utc::now(),
             status: "pending".to_string(),
             transaction_signature: None,
+            fee_sol: 0.0,
+            execution: CopyExecution::from_leader(original_trade, detected_at),
         };
     
-        // Execute the copy trade
-        let result = self.execute_copy_trade_transaction(&copy_trade).await?;
+        // Buys must clear the portfolio limits; sells only ever reduce exposure
+        let _permit = if copy_trade.trade_type == "buy" {
+            match self.risk.check_buy("copy_trader", &copy_trade.token_mint, copy_amount).await {
+                Ok(permit) => Some(permit),
+                Err(rejection) => {
+                    warn!("Not copying {} buy of {}: {}", trader.name, copy_trade.token_mint, rejection);
+                    self.metrics.increment_counter("copy_risk_rejections", 1).await;
+                    return Ok(None);
+                }
+            }
+        } else {
+            None
+        };
+        
+        // Execute the copy trade; errors count towards the failure breaker too
+        let result = match self.execute_copy_trade_transaction(&copy_trade).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.risk.record_trade_result(false).await;
+                return Err(e);
+            }
+        };
+        self.risk.record_trade_result(result.success).await;
+        copy_trade.execution.sent_at = result.sent_at;
         
         if result.success {
-            // Record successful copy trade
-            self.active_copy_trades.write().await.insert(
-                copy_trade.id.clone(),
-                copy_trade.clone(),
-            );
+            let signature = result.signature.as_deref()
+                .ok_or_else(|| BotError::Transaction(format!("Copy of {} landed without a signature", copy_trade.token_mint)))?;
+            if let Err(e) = self.heaven_client.settle_copy_trade(&mut copy_trade, signature).await {
+                self.database.record_copy_trade(&copy_trade).await?;
+                return Err(e);
+            }
+            if let Some(slippage) = copy_trade.execution.slippage_vs_leader(&copy_trade.trade_type) {
+                debug!("Copy of {} filled {:.2}% off the leader", trader.name, slippage * 100.0);
+            }
+            
+            // Track the resulting position, or close ours when the leader exits
+            match copy_trade.trade_type.as_str() {
+                "buy" => {
+                    let strategy = if consensus { "Consensus" } else { "CopyTrade" };
+                    let mut position = new_position(
+                        copy_trade.id.clone(),
+                        &copy_trade.token_mint,
+                        "copy_trader",
+                        SniperStrategy::Custom(strategy.to_string()),
+                        copy_trade.price,
+                        copy_trade.amount_sol + copy_trade.fee_sol,
+                        copy_trade.token_amount,
+                    );
+                    if !consensus {
+                        position.trader_address = Some(trader.address.clone());
+                    }
+                    self.positions.open_position(position, copy_trade.fee_sol).await?;
+                }
+                "sell" => {
+                    let sol_received = copy_trade.amount_sol - copy_trade.fee_sol;
+                    self.reduce_leader_positions(
+                        &trader.address,
+                        &copy_trade.token_mint,
+                        copy_trade.token_amount,
+                        sol_received,
+                        copy_trade.fee_sol,
+                    ).await?;
+                }
+                _ => {}
+            }
+            
+            // Record successful copy trade; only buys leave an open position
+            if copy_trade.trade_type == "buy" {
+                self.active_copy_trades.write().await.insert(
+                    copy_trade.id.clone(),
+                    copy_trade.clone(),
+                );
+            }
             
             // Record in database
             self.database.record_copy_trade(&copy_trade).await?;
             
             // Update metrics
-            self.metrics.record_successful_copy_trade(copy_amount).await;
+            self.metrics.record_successful_copy_trade(copy_trade.amount_sol).await;
             
-            info!("Successfully copied trade from {}: {} SOL", trader.name, copy_amount);
+            info!("Successfully copied trade from {}: {} SOL", trader.name, copy_trade.amount_sol);
+            
+            if copy_trade.trade_type == "buy" {
+                return Ok(Some(copy_trade));
+            }
         } else {
             error!("Failed to copy trade from {}: {}", trader.name, result.error.unwrap_or_default());
             self.metrics.record_failed_copy_trade(copy_amount).await;
         }
         
+        Ok(None)
+    }
+    
+    async fn copy_buy_amount(&self, original_trade: &Trade, trader: &Trader) -> Result<f64, BotError> {
+        let overrides = self.trader_config(&trader.address);
+        let max_sol = overrides
+            .and_then(|t| t.max_sol_per_trade)
+            .unwrap_or(self.config.copy_trader.max_sol_per_trade);
+        let min_sol = overrides.and_then(|t| t.min_sol_per_trade).unwrap_or(0.0);
+        
+        let amount = match self.config.copy_trader.sizing_mode {
+            CopySizingMode::Fixed => {
+                let leader_amount = original_trade.amount_sol * self.config.copy_trader.copy_percentage;
+                self.sizer.size(&SizingRequest {
+                    token_mint: &original_trade.token_mint,
+                    strategy: &SniperStrategy::Custom("CopyTrade".to_string()),
+                    policy: None,
+                    multiplier: 1.0,
+                    stop_loss: ExitPlan::new(&self.config.copy_trader.exit, &self.config.trading).stop_loss(),
+                    max_sol: leader_amount.min(max_sol),
+                }).await?
+            }
+            CopySizingMode::Proportional => {
+                let fraction = self.leader_buy_fraction(original_trade, trader).await?;
+                let amount = self.sizer.equity().await? * fraction;
+                amount.min(max_sol).min(self.sizer.liquidity_cap(&original_trade.token_mint).await?)
+            }
+        };
+        
+        let amount = if self.trader_status(&trader.address).await == TraderStatus::Probation {
+            amount * self.config.copy_trader.evaluation.probation_size_fraction
+        } else {
+            amount
+        };
+        
+        if amount <= 0.0 || amount >= min_sol {
+            return Ok(amount.max(0.0));
+        }
+        
+        // Small copies are lifted to the floor, or skipped if the caps do not leave room for it
+        let ceiling = max_sol.min(self.sizer.liquidity_cap(&original_trade.token_mint).await?);
+        Ok(if min_sol <= ceiling { min_sol } else { 0.0 })
+    }
+    
+    /// Trim a buy to what is left of the trader's budget, or to nothing once
+    /// their copies hold as many positions as allowed.
+    async fn fit_trader_allocation(&self, trader: &Trader, amount: f64) -> Result<f64, BotError> {
+        let open: Vec<_> = self.positions.open_positions_by_source("copy_trader").await
+            .into_iter()
+            .filter(|p| p.trader_address.as_deref() == Some(trader.address.as_str()))
+            .collect();
+        
+        if let Some(max_open) = self.trader_config(&trader.address).and_then(|t| t.max_open_positions) {
+            if open.len() >= max_open {
+                debug!("{} already has {} open copied positions", trader.name, open.len());
+                return Ok(0.0);
+            }
+        }
+        
+        let Some(budget) = self.trader_budget(&trader.address).await? else {
+            return Ok(amount);
+        };
+        let committed: f64 = open.iter().map(|p| p.amount_sol).sum();
+        Ok(amount.min(budget - committed).max(0.0))
+    }
+    
+    /// SOL that copies of this trader may have open, if limited. With weighting
+    /// on, the trader's share of the weighted budget, capped by their own budget.
+    async fn trader_budget(&self, trader_address: &str) -> Result<Option<f64>, BotError> {
+        let fixed = self.trader_config(trader_address).and_then(|t| t.budget_sol);
+        let weighting = &self.config.copy_trader.weighting;
+        if !weighting.enabled {
+            return Ok(fixed);
+        }
+        
+        let weight = self.trader_weights.read().await.get(trader_address).copied().unwrap_or(0.0);
+        let weighted = weighting.total_budget_sol * weight;
+        Ok(Some(fixed.map_or(weighted, |budget| budget.min(weighted))))
+    }
+    
+    /// Weigh tracked traders by the return of our own copies of them closed
+    /// within the window. Traders without closed copies score as break-even.
+    async fn refresh_trader_weights(&self) -> Result<(), BotError> {
+        let weighting = &self.config.copy_trader.weighting;
+        let since = Utc::now() - Duration::hours(weighting.window_hours as i64);
+        
+        let mut copy_pnl: HashMap<String, (f64, f64)> = HashMap::new();
+        for trade in self.database.get_closed_trades(Some(since)).await? {
+            if let Some(address) = trade.trader_address {
+                let (pnl, cost) = copy_pnl.entry(address).or_default();
+                *pnl += trade.realized_pnl;
+                *cost += trade.cost_sol;
+            }
+        }
+        
+        let scores: Vec<(String, f64)> = self.tracked_traders.read().await.keys()
+            .map(|address| {
+                let copy_return = match copy_pnl.get(address) {
+                    Some((pnl, cost)) if *cost > 0.0 => pnl / cost,
+                    _ => 0.0,
+                };
+                (address.clone(), (1.0 + copy_return).max(weighting.min_score))
+            })
+            .collect();
+        let total: f64 = scores.iter().map(|(_, score)| score).sum();
+        
+        *self.trader_weights.write().await = scores.into_iter()
+            .map(|(address, score)| (address, if total > 0.0 { score / total } else { 0.0 }))
+            .collect();
         Ok(())
     }
     
+    /// Share of the leader's equity spent on this buy. Their SOL balance is read
+    /// after the buy, so the spend is added back; tokens they hold are not valued.
+    async fn leader_buy_fraction(&self, trade: &Trade, trader: &Trader) -> Result<f64, BotError> {
+        let spent = if trade.sol_spent > 0.0 { trade.sol_spent } else { trade.amount_sol };
+        let equity = self.heaven_client.get_wallet_sol_balance(&trader.address).await? + spent;
+        
+        Ok(if equity > 0.0 { (spent / equity).min(1.0) } else { 0.0 })
+    }
+    
+    /// Share of their bag the leader sold, from the balances in their own
+    /// transaction. Without them the copy is skipped rather than guessed.
+    fn leader_sell_fraction(&self, trade: &Trade, trader: &Trader) -> Result<f64, BotError> {
+        match trade.token_balance_before {
+            Some(held) if held > 0.0 => Ok((trade.token_amount / held).min(1.0)),
+            _ => Err(BotError::Validation(format!(
+                "Unknown {} balance of {} before sell {}", trade.token_mint, trader.name, trade.id
+            ))),
+        }
+    }
+    
+    async fn leader_position_tokens(&self, trader_address: &str, token_mint: &str) -> f64 {
+        self.positions.open_positions_by_source("copy_trader").await
+            .iter()
+            .filter(|p| p.token_mint == token_mint && p.trader_address.as_deref() == Some(trader_address))
+            .map(|p| p.token_amount)
+            .sum()
+    }
+    
     async fn execute_copy_trade_transaction(&self, copy_trade: &CopyTrade) -> Result<CopyTradeResult, BotError> {
         // Create and execute the trade based on type
         match copy_trade.trade_type.as_str() {
@@ -297,6 +999,7 @@ impl CopyTraderBot {
         
         // Execute transaction
         let transaction = self.create_signed_transaction(&[buy_ix]).await?;
+        let sent_at = Utc::now();
         let signature = self.rpc_client.send_and_confirm_transaction(&transaction)?;
         
         // Check transaction status
@@ -305,6 +1008,7 @@ impl CopyTraderBot {
         Ok(CopyTradeResult {
             success: status.is_ok(),
             signature: Some(signature.to_string()),
+            sent_at: Some(sent_at),
             error: if status.is_err() { 
                 Some(format!("{:?}", status.unwrap_err())) 
             } else { 
@@ -314,26 +1018,32 @@ impl CopyTraderBot {
     }
     
     async fn execute_copy_sell(&self, copy_trade: &CopyTrade) -> Result<CopyTradeResult, BotError> {
-        // Get current token balance
+        // Sell the requested amount, or everything when mirroring a leader exit
         let token_balance = self.heaven_client.get_token_balance(&copy_trade.token_mint).await?;
+        let token_amount = if copy_trade.token_amount > 0.0 {
+            copy_trade.token_amount.min(token_balance)
+        } else {
+            token_balance
+        };
         
         // Get sell quote
         let quote = self.heaven_client.get_sell_quote(
             &copy_trade.token_mint,
-            token_balance,
+            token_amount,
             copy_trade.slippage,
         ).await?;
         
         // Create sell instruction
         let sell_ix = self.heaven_client.create_sell_instruction(
             &copy_trade.token_mint,
-            token_balance,
+            token_amount,
             quote.sol_amount,
             &self.wallet.pubkey(),
         ).await?;
         
         // Execute transaction
         let transaction = self.create_signed_transaction(&[sell_ix]).await?;
+        let sent_at = Utc::now();
         let signature = self.rpc_client.send_and_confirm_transaction(&transaction)?;
         
         // Check transaction status
@@ -342,6 +1052,7 @@ impl CopyTraderBot {
         Ok(CopyTradeResult {
             success: status.is_ok(),
             signature: Some(signature.to_string()),
+            sent_at: Some(sent_at),
             error: if status.is_err() { 
                 Some(format!("{:?}", status.unwrap_err())) 
             } else { 
@@ -367,13 +1078,17 @@ impl CopyTraderBot {
                 continue;
             }
             
-            // Check if we should close the position
-            if self.should_close_copy_trade(copy_trade).await {
-                if let Err(e) = self.close_copy_trade(copy_trade).await {
-                    warn!("Failed to close copy trade {}: {}", trade_id, e);
-                } else {
-                    copy_trade.status = "completed".to_string();
-                    to_remove.push(trade_id.clone());
+            // Check if we should reduce or close the position, or sell the next slice of a split exit
+            let signal = self.copy_trade_exit_signal(copy_trade).await;
+            if let Some(slice) = self.next_exit_slice(copy_trade, signal.as_ref()).await {
+                match self.close_copy_trade(copy_trade, &slice).await {
+                    Ok(true) => {
+                        copy_trade.status = "completed".to_string();
+                        to_remove.push(trade_id.clone());
+                        self.consensus.write().await.forget(trade_id);
+                    }
+                    Ok(false) => {}
+                    Err(e) => warn!("Failed to close copy trade {}: {}", trade_id, e),
                 }
             }
         }
@@ -386,34 +1101,77 @@ impl CopyTraderBot {
         Ok(())
     }
     
-    async fn should_close_copy_trade(&self, copy_trade: &CopyTrade) -> bool {
+    async fn copy_trade_exit_signal(&self, copy_trade: &CopyTrade) -> Option<ExitSignal> {
+        if self.risk.is_flattening().await {
+            return Some(ExitSignal::full(ExitReason::KillSwitch));
+        }
+        
+        if self.fade.sell_due(&copy_trade.token_mint).await {
+            return Some(ExitSignal::full(ExitReason::Faded));
+        }
+        
+        // Enough of the traders behind a consensus entry have sold
+        if self.consensus.read().await.exit_due(&copy_trade.id) {
+            return Some(ExitSignal::full(ExitReason::LeaderExit));
+        }
+        
         // Check if original trader has closed their position
         if let Ok(original_trade) = self.database.get_trade(&copy_trade.original_trade_id).await {
             if original_trade.status == "closed" || original_trade.status == "sold" {
-                return true;
+                return Some(ExitSignal::full(ExitReason::LeaderExit));
             }
         }
         
-        // Check profit/loss thresholds
-        if let Ok(current_price) = self.heaven_client.get_token_price(&copy_trade.token_mint).await {
-            let price_change = (current_price - copy_trade.price) / copy_trade.price;
-            
-            // Close if profit target reached or stop loss hit
-            price_change >= self.config.trading.profit_taking_percentage ||
-            price_change <= -self.config.trading.stop_loss_percentage
-        } else {
-            false
+        // Check exit rules against the current price
+        let current_price = self.heaven_client.get_token_price(&copy_trade.token_mint).await.ok()?;
+        match self.positions.update_price(&copy_trade.id, current_price).await {
+            Ok(Some(position)) => {
+                ExitPlan::new(&self.config.copy_trader.exit, &self.config.trading).evaluate(&position)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to update position for copy trade {}: {}", copy_trade.id, e);
+                None
+            }
         }
     }
     
-    async fn close_copy_trade(&self, copy_trade: &CopyTrade) -> Result<(), BotError> {
-        info!("Closing copy trade: {} {}", copy_trade.trade_type, copy_trade.token_mint);
+    async fn next_exit_slice(&self, copy_trade: &CopyTrade, signal: Option<&ExitSignal>) -> Option<ExitSlice> {
+        if signal.is_none() && !self.exit_slicer.read().await.is_pending(&copy_trade.id) {
+            return None;
+        }
+        
+        let position = self.positions.get_position(&copy_trade.id).await?;
+        let max_exit_impact = self.config.trading.execution.max_exit_impact;
+        let max_tokens = match self.heaven_client.max_sell_for_impact(&copy_trade.token_mint, max_exit_impact).await {
+            Ok(max_tokens) => max_tokens,
+            Err(e) => {
+                warn!("No pool depth for exit from {}: {}", copy_trade.token_mint, e);
+                return None;
+            }
+        };
+        
+        self.exit_slicer.write().await.next_slice(&position, signal, max_tokens)
+    }
+    
+    /// Sell one exit slice of a copied buy. Returns whether the position is now closed.
+    async fn close_copy_trade(&self, copy_trade: &CopyTrade, slice: &ExitSlice) -> Result<bool, BotError> {
+        info!(
+            "Selling {:.4} tokens of copy trade: {} {} ({:?})",
+            slice.tokens, copy_trade.trade_type, copy_trade.token_mint, slice.reason
+        );
         
         // Execute opposite trade to close position
         match copy_trade.trade_type.as_str() {
             "buy" => {
+                let Some(position) = self.positions.get_position(&copy_trade.id).await else {
+                    self.exit_slicer.write().await.forget(&copy_trade.id);
+                    return Ok(true);
+                };
+                let token_amount = slice.tokens;
+                
                 // Sell to close long position
-                let sell_trade = CopyTrade {
+                let mut sell_trade = CopyTrade {
                     id: uuid::Uuid::new_v4().to_string(),
                     original_trade_id: copy_trade.id.clone(),
                     trader_address: copy_trade.trader_address.clone(),
@@ -421,22 +1179,83 @@ impl CopyTraderBot {
                     token_mint: copy_trade.token_mint.clone(),
                     trade_type: "sell".to_string(),
                     amount_sol: 0.0,
-                    token_amount: copy_trade.token_amount,
+                    token_amount,
                     price: copy_trade.price,
                     slippage: copy_trade.slippage,
                     timestamp: Utc::now(),
                     status: "pending".to_string(),
                     transaction_signature: None,
+                    fee_sol: 0.0,
+                    execution: CopyExecution::default(),
                 };
                 
-                self.execute_copy_trade_transaction(&sell_trade).await?;
+                let result = self.execute_copy_trade_transaction(&sell_trade).await?;
+                if !result.success {
+                    return Ok(false);
+                }
+                
+                // Proceeds are booked only from a readable fill; the position stays open otherwise
+                let signature = result.signature.as_deref()
+                    .ok_or_else(|| BotError::Transaction(format!("Sell of {} landed without a signature", copy_trade.token_mint)))?;
+                if let Err(e) = self.heaven_client.settle_copy_trade(&mut sell_trade, signature).await {
+                    self.database.record_copy_trade(&sell_trade).await?;
+                    return Err(e);
+                }
+                sell_trade.status = "completed".to_string();
+                self.database.record_copy_trade(&sell_trade).await?;
+                
+                let sol_received = sell_trade.amount_sol - sell_trade.fee_sol;
+                let tokens_sold = if slice.closes { position.token_amount } else { sell_trade.token_amount };
+                let updated = self.positions.reduce_position(&copy_trade.id, tokens_sold, sol_received, sell_trade.fee_sol).await?;
+                if let ExitReason::TakeProfit(level) = slice.reason {
+                    self.positions.record_take_profit(&copy_trade.id, level).await?;
+                }
+                
+                let closed = updated.map(|p| p.status == "closed").unwrap_or(true);
+                let mut exit_slicer = self.exit_slicer.write().await;
+                if closed {
+                    exit_slicer.forget(&copy_trade.id);
+                } else {
+                    exit_slicer.record_sold(&copy_trade.id, tokens_sold);
+                }
+                
+                Ok(closed)
             }
-            "sell" => {
-                // Buy to close short position (if supported)
-                // For now, just mark as completed
-                copy_trade.status = "completed".to_string();
+            // Sells have no position of ours to unwind
+            _ => Ok(true),
+        }
+    }
+    
+    /// Spread a mirrored leader sell across the positions copied from them, by size.
+    async fn reduce_leader_positions(
+        &self,
+        trader_address: &str,
+        token_mint: &str,
+        tokens_sold: f64,
+        sol_received: f64,
+        fee_sol: f64,
+    ) -> Result<(), BotError> {
+        let positions: Vec<_> = self.positions.open_positions_by_source("copy_trader").await
+            .into_iter()
+            .filter(|p| p.token_mint == token_mint && p.trader_address.as_deref() == Some(trader_address))
+            .collect();
+        
+        // Split proceeds across positions by size
+        let total_tokens: f64 = positions.iter().map(|p| p.token_amount).sum();
+        let mut active_trades = self.active_copy_trades.write().await;
+        for position in positions {
+            let share = if total_tokens > 0.0 { position.token_amount / total_tokens } else { 0.0 };
+            let updated = self.positions.reduce_position(
+                &position.id,
+                tokens_sold * share,
+                sol_received * share,
+                fee_sol * share,
+            ).await?;
+            
+            if updated.map(|p| p.status == "closed").unwrap_or(true) {
+                active_trades.remove(&position.id);
+                self.exit_slicer.write().await.forget(&position.id);
             }
-            _ => {}
         }
         
         Ok(())
@@ -447,7 +1266,7 @@ impl CopyTraderBot {
         
         for (address, perf) in performance.iter_mut() {
             // Get updated trader data
-            if let Ok(trader) = self.database.get_trader(address).await {
+            if let Ok(Some(trader)) = self.database.get_trader(address).await {
                 perf.total_trades = trader.total_trades;
                 perf.successful_trades = trader.successful_trades;
                 perf.total_profit = trader.total_profit;
@@ -460,6 +1279,94 @@ impl CopyTraderBot {
         Ok(())
     }
     
+    /// Score our recent copies of each tracked trader and move those under the
+    /// thresholds to probation or the blacklist, or back off probation.
+    async fn evaluate_traders(&self) -> Result<(), BotError> {
+        let settings = &self.config.copy_trader.evaluation;
+        let since = Utc::now() - Duration::days(settings.window_days as i64);
+        let trades = self.database.get_closed_trades(Some(since)).await?;
+        
+        // Each change restarts the trader's window
+        let statuses = self.trader_statuses.read().await.clone();
+        let scores = score_copies(&trades, settings, |address| {
+            statuses.get(address).map(|change| change.changed_at)
+        });
+        
+        let tracked: Vec<String> = self.tracked_traders.read().await.keys().cloned().collect();
+        for address in tracked {
+            let Some(score) = scores.get(&address) else {
+                continue;
+            };
+            let current = self.trader_status(&address).await;
+            let (status, reason) = next_status(current, score, settings);
+            if status != current {
+                self.change_trader_status(&address, status, reason, score).await?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn trader_status(&self, address: &str) -> TraderStatus {
+        self.trader_statuses.read().await
+            .get(address)
+            .map_or(TraderStatus::Active, |change| change.to_status)
+    }
+    
+    /// Move a trader to `status` by hand, e.g. to reinstate a blacklisted trader.
+    pub async fn set_trader_status(&self, address: &str, status: TraderStatus, reason: &str) -> Result<(), BotError> {
+        if self.trader_status(address).await == status {
+            return Ok(());
+        }
+        
+        self.change_trader_status(address, status, reason.to_string(), &CopyScore::default()).await?;
+        if status != TraderStatus::Blacklisted {
+            self.initialize_tracked_traders().await?;
+        }
+        
+        Ok(())
+    }
+    
+    async fn change_trader_status(
+        &self,
+        address: &str,
+        status: TraderStatus,
+        reason: String,
+        score: &CopyScore,
+    ) -> Result<(), BotError> {
+        let change = TraderStatusChange {
+            trader_address: address.to_string(),
+            from_status: self.trader_status(address).await,
+            to_status: status,
+            reason,
+            copies: score.copies,
+            copy_return: score.copy_return(),
+            win_rate: score.win_rate(),
+            changed_at: Utc::now(),
+        };
+        self.database.record_trader_status_change(&change).await?;
+        
+        warn!(
+            "Trader {} moved from {} to {}: {}",
+            address, change.from_status.name(), change.to_status.name(), change.reason
+        );
+        let severity = match status {
+            TraderStatus::Blacklisted => EventSeverity::High,
+            TraderStatus::Probation => EventSeverity::Medium,
+            TraderStatus::Active => EventSeverity::Info,
+        };
+        self.risk.emit(EventType::TraderStatusChanged, severity, serde_json::json!(change));
+        
+        // Open copies of a blacklisted trader are left to their exit rules
+        if status == TraderStatus::Blacklisted {
+            self.tracked_traders.write().await.remove(address);
+            self.trader_performance.write().await.remove(address);
+        }
+        self.trader_statuses.write().await.insert(address.to_string(), change);
+        
+        Ok(())
+    }
+    
     async fn update_copy_trading_metrics(&self) {
         let active_trades = self.active_copy_trades.read().await;
         let tracked_traders = self.tracked_traders.read().await;
@@ -486,6 +1393,7 @@ impl CopyTraderBot {
 pub struct CopyTradeResult {
     pub success: bool,
     pub signature: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
     pub error: Option<String>,
 }
 
@@ -0,0 +1,289 @@
+use crate::{
+    config::CreatorReputationConfig,
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::Database,
+    types::{CreatorLaunch, CreatorReputation, TokenLaunch},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tracing::{info, warn, debug};
+use chrono::{Duration, Utc};
+
+/// Follows each creator's launches to their outcome and keeps a reputation
+/// score per creator that the sniper filters launches on.
+pub struct CreatorTracker {
+    config: CreatorReputationConfig,
+    verified_creators: Vec<String>,
+    heaven_client: Arc<HeavenClient>,
+    database: Arc<Database>,
+    reputations: RwLock<HashMap<String, CreatorReputation>>,
+    // Launches queued by the sniper, recorded by `run` off the snipe path
+    launch_sender: mpsc::UnboundedSender<TokenLaunch>,
+    launch_receiver: Mutex<mpsc::UnboundedReceiver<TokenLaunch>>,
+}
+
+impl CreatorTracker {
+    pub fn new(
+        config: CreatorReputationConfig,
+        verified_creators: Vec<String>,
+        heaven_client: Arc<HeavenClient>,
+        database: Arc<Database>,
+    ) -> Self {
+        let (launch_sender, launch_receiver) = mpsc::unbounded_channel();
+        
+        Self {
+            config,
+            verified_creators,
+            heaven_client,
+            database,
+            reputations: RwLock::new(HashMap::new()),
+            launch_sender,
+            launch_receiver: Mutex::new(launch_receiver),
+        }
+    }
+    
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+    
+    pub async fn load(&self) -> Result<(), BotError> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        
+        let reputations = self.database.get_creator_reputations().await?;
+        info!("Loaded reputations of {} creators", reputations.len());
+        *self.reputations.write().await = reputations.into_iter()
+            .map(|r| (r.address.clone(), r))
+            .collect();
+        
+        Ok(())
+    }
+    
+    pub async fn run(&self, is_running: Arc<RwLock<bool>>) -> Result<(), BotError> {
+        let mut interval = tokio::time::interval(
+            std::time::Duration::from_secs(self.config.interval_secs)
+        );
+        let mut launches = self.launch_receiver.lock().await;
+        
+        while *is_running.read().await {
+            tokio::select! {
+                Some(launch) = launches.recv() => {
+                    if let Err(e) = self.record_launch(&launch).await {
+                        warn!("Failed to record creator of {}: {}", launch.token_mint, e);
+                    }
+                    continue;
+                }
+                _ = interval.tick() => {}
+            }
+            
+            if let Err(e) = self.update_launches().await {
+                warn!("Failed to update creator launches: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Queue a launch by a known creator to be watched. Reading the creator's
+    /// holdings takes an RPC round trip, so it is left to `run`.
+    pub fn watch_launch(&self, launch: &TokenLaunch) {
+        if !self.config.enabled || launch.creator_address.is_none() {
+            return;
+        }
+        let _ = self.launch_sender.send(launch.clone());
+    }
+    
+    /// Start watching a launch by a known creator.
+    async fn record_launch(&self, launch: &TokenLaunch) -> Result<(), BotError> {
+        let Some(creator) = &launch.creator_address else {
+            return Ok(());
+        };
+        
+        let initial_price = if launch.initial_price > 0.0 { launch.initial_price } else { launch.price };
+        let creator_initial_tokens = self.heaven_client
+            .get_wallet_token_balance(creator, &launch.token_mint).await?;
+        
+        let recorded = self.database.record_creator_launch(&CreatorLaunch {
+            token_mint: launch.token_mint.clone(),
+            creator_address: creator.clone(),
+            launch_time: launch.launch_time,
+            initial_price,
+            peak_price: launch.price.max(initial_price),
+            creator_initial_tokens,
+            creator_sold_share: 0.0,
+            rugged_at: None,
+            settled: false,
+        }).await?;
+        if recorded {
+            debug!("Watching launch {} by creator {}", launch.token_mint, creator);
+        }
+        
+        Ok(())
+    }
+    
+    /// Track peak price, rugs and creator sells of watched launches, and
+    /// rescore the creators of launches that settle. A launch whose pool is
+    /// gone counts as rugged; one without market data still settles once its
+    /// watch window is over.
+    async fn update_launches(&self) -> Result<(), BotError> {
+        let now = Utc::now();
+        let watch = Duration::hours(self.config.watch_hours as i64);
+        
+        for mut launch in self.database.get_watched_creator_launches().await? {
+            let watch_over = now - launch.launch_time >= watch;
+            match self.heaven_client.get_market_data(&launch.token_mint).await {
+                Ok(market_data) => {
+                    launch.peak_price = launch.peak_price.max(market_data.price);
+                    if launch.peak_price > 0.0 && market_data.price <= launch.peak_price * (1.0 - self.config.rug_drawdown) {
+                        launch.rugged_at = Some(now);
+                    }
+                }
+                Err(BotError::PoolNotFound(_)) => launch.rugged_at = Some(now),
+                Err(e) => {
+                    debug!("No market data for creator launch {}: {}", launch.token_mint, e);
+                    if !watch_over {
+                        continue;
+                    }
+                }
+            }
+            
+            if launch.creator_initial_tokens > 0.0 {
+                if let Ok(held) = self.heaven_client
+                    .get_wallet_token_balance(&launch.creator_address, &launch.token_mint).await
+                {
+                    let sold = (1.0 - held / launch.creator_initial_tokens).clamp(0.0, 1.0);
+                    launch.creator_sold_share = launch.creator_sold_share.max(sold);
+                }
+            }
+            
+            launch.settled = launch.rugged_at.is_some() || watch_over;
+            self.database.update_creator_launch(&launch).await?;
+            
+            if launch.settled {
+                if launch.rugged_at.is_some() {
+                    warn!(
+                        "Launch {} by {} rugged after {}s",
+                        launch.token_mint, launch.creator_address, launch.secs_to_rug().unwrap_or(0)
+                    );
+                }
+                self.rescore(&launch.creator_address).await?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn rescore(&self, creator: &str) -> Result<(), BotError> {
+        let launches: Vec<CreatorLaunch> = self.database.get_creator_launches(creator).await?
+            .into_iter()
+            .filter(|l| l.settled)
+            .collect();
+        let reputation = score_creator(creator, &launches, &self.config);
+        
+        let was_blacklisted = self.reputations.read().await
+            .get(creator)
+            .is_some_and(|r| r.blacklisted);
+        if reputation.blacklisted && !was_blacklisted {
+            warn!("Blacklisting creator {} after {} rugs", creator, reputation.rugs);
+        }
+        
+        self.database.record_creator_reputation(&reputation).await?;
+        self.reputations.write().await.insert(creator.to_string(), reputation);
+        
+        Ok(())
+    }
+    
+    pub async fn reputation(&self, creator: &str) -> Option<CreatorReputation> {
+        self.reputations.read().await.get(creator).cloned()
+    }
+    
+    pub async fn score(&self, creator: &str) -> f64 {
+        self.reputations.read().await
+            .get(creator)
+            .map_or(self.config.unknown_score, |r| r.score)
+    }
+    
+    /// Whether the sniper may buy a launch. Launches without a known creator pass.
+    pub async fn allows(&self, launch: &TokenLaunch) -> bool {
+        let Some(creator) = &launch.creator_address else {
+            return true;
+        };
+        if !self.config.enabled {
+            return true;
+        }
+        
+        let reputations = self.reputations.read().await;
+        let reputation = reputations.get(creator);
+        if reputation.is_some_and(|r| r.blacklisted) {
+            return false;
+        }
+        
+        let score = reputation.map_or(self.config.unknown_score, |r| r.score);
+        self.config.min_score.map_or(true, |min_score| score >= min_score)
+    }
+    
+    /// Listed in `verified_creators`, or an established creator with a high score.
+    pub async fn is_verified(&self, creator: &str) -> bool {
+        if self.verified_creators.iter().any(|c| c == creator) {
+            return true;
+        }
+        if !self.config.enabled {
+            return false;
+        }
+        
+        self.reputations.read().await.get(creator).is_some_and(|r| {
+            !r.blacklisted
+                && r.launches >= self.config.verified_min_launches
+                && r.score >= self.config.verified_min_score
+        })
+    }
+}
+
+/// Score between 0 and 1 from how often the creator rugged, how much they
+/// sold, and how high their launches went. Few launches pull the score
+/// towards `unknown_score`.
+pub fn score_creator(
+    creator: &str,
+    launches: &[CreatorLaunch],
+    settings: &CreatorReputationConfig,
+) -> CreatorReputation {
+    let count = launches.len() as f64;
+    let rug_times: Vec<i64> = launches.iter().filter_map(|l| l.secs_to_rug()).collect();
+    let rugs = rug_times.len() as u64;
+    
+    let average = |values: Vec<f64>| {
+        if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+    };
+    let average_peak_multiple = average(launches.iter().map(|l| l.peak_multiple()).collect());
+    let average_sold_share = average(launches.iter().map(|l| l.creator_sold_share).collect());
+    let average_secs_to_rug = if rug_times.is_empty() {
+        None
+    } else {
+        Some(average(rug_times.iter().map(|&secs| secs as f64).collect()))
+    };
+    
+    let score = if launches.is_empty() {
+        settings.unknown_score
+    } else {
+        let rug_rate = rugs as f64 / count;
+        // A 10x peak counts as full upside
+        let upside = average_peak_multiple.max(1.0).log10().min(1.0);
+        let raw = 0.6 * (1.0 - rug_rate) + 0.2 * (1.0 - average_sold_share) + 0.2 * upside;
+        ((raw * count + settings.unknown_score) / (count + 1.0)).clamp(0.0, 1.0)
+    };
+    
+    CreatorReputation {
+        address: creator.to_string(),
+        launches: launches.len() as u64,
+        rugs,
+        average_peak_multiple,
+        average_sold_share,
+        average_secs_to_rug,
+        score,
+        blacklisted: rugs >= settings.blacklist_rugs,
+        updated_at: Utc::now(),
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
     error::BotError,
-    types::{Trade, TokenLaunch, CopyTrade, Trader, Bundle, BundleResult},
+    types::{Trade, TokenLaunch, CopyTrade, Trader, Bundle, BundleResult, SnipeTarget, SnipePriority, SniperStrategy, Position, Lot, LotClosure, PnlGroup, PnlBreakdown, FadedWallet, CopyExecution, CopySlippageGroup, CopySlippageBreakdown, TraderStatus, TraderStatusChange, CreatorLaunch, CreatorReputation},
 };
 use sqlx::{sqlite::SqlitePool, Row};
+use std::collections::BTreeMap;
 use chrono::{DateTime, Utc};
 use tracing::{info, warn, error};
 
@@ -37,6 +38,9 @@ impl Database {
                 timestamp TEXT NOT NULL,
                 status TEXT NOT NULL,
                 transaction_signature TEXT,
+                fee_sol REAL NOT NULL,
+                sol_spent REAL NOT NULL,
+                sol_received REAL NOT NULL,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )
@@ -84,6 +88,14 @@ impl Database {
                 timestamp TEXT NOT NULL,
                 status TEXT NOT NULL,
                 transaction_signature TEXT,
+                fee_sol REAL NOT NULL,
+                leader_signature TEXT,
+                leader_slot INTEGER,
+                leader_price REAL NOT NULL DEFAULT 0,
+                detected_at TEXT,
+                sent_at TEXT,
+                landed_slot INTEGER,
+                fill_price REAL NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )
@@ -143,6 +155,193 @@ impl Database {
             "#
         ).execute(&self.pool).await?;
         
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS snipe_targets (
+                token_mint TEXT PRIMARY KEY,
+                strategy TEXT NOT NULL,
+                target_price REAL NOT NULL,
+                max_amount REAL NOT NULL,
+                priority TEXT NOT NULL,
+                conditions TEXT NOT NULL,
+                is_active BOOLEAN NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS positions (
+                id TEXT PRIMARY KEY,
+                token_mint TEXT NOT NULL,
+                source TEXT NOT NULL,
+                trader_address TEXT,
+                strategy TEXT NOT NULL,
+                entry_price REAL NOT NULL,
+                current_price REAL NOT NULL,
+                amount_sol REAL NOT NULL,
+                token_amount REAL NOT NULL,
+                unrealized_pnl REAL NOT NULL,
+                realized_pnl REAL NOT NULL,
+                stop_loss REAL,
+                take_profit REAL,
+                status TEXT NOT NULL,
+                initial_token_amount REAL NOT NULL,
+                high_water_price REAL NOT NULL,
+                take_profits_hit INTEGER NOT NULL,
+                entry_time TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS lots (
+                id TEXT PRIMARY KEY,
+                token_mint TEXT NOT NULL,
+                source TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                trader_address TEXT,
+                token_amount REAL NOT NULL,
+                remaining_tokens REAL NOT NULL,
+                cost_sol REAL NOT NULL,
+                remaining_cost_sol REAL NOT NULL,
+                fee_sol REAL NOT NULL,
+                entry_time TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS lot_closures (
+                id TEXT PRIMARY KEY,
+                lot_id TEXT NOT NULL,
+                token_mint TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                trader_address TEXT,
+                tokens REAL NOT NULL,
+                cost_sol REAL NOT NULL,
+                proceeds_sol REAL NOT NULL,
+                fee_sol REAL NOT NULL,
+                realized_pnl REAL NOT NULL,
+                closed_at TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS seen_trader_trades (
+                trader_address TEXT NOT NULL,
+                signature TEXT NOT NULL,
+                trade_time TEXT NOT NULL,
+                seen_at TEXT NOT NULL,
+                PRIMARY KEY (trader_address, signature)
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS trader_watermarks (
+                trader_address TEXT PRIMARY KEY,
+                last_trade_time TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS faded_wallets (
+                address TEXT PRIMARY KEY,
+                realized_pnl REAL NOT NULL,
+                round_trips INTEGER NOT NULL,
+                win_rate REAL NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS trader_status_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trader_address TEXT NOT NULL,
+                from_status TEXT NOT NULL,
+                to_status TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                copies INTEGER NOT NULL,
+                copy_return REAL NOT NULL,
+                win_rate REAL NOT NULL,
+                changed_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS creator_launches (
+                token_mint TEXT PRIMARY KEY,
+                creator_address TEXT NOT NULL,
+                launch_time TEXT NOT NULL,
+                initial_price REAL NOT NULL,
+                peak_price REAL NOT NULL,
+                creator_initial_tokens REAL NOT NULL,
+                creator_sold_share REAL NOT NULL,
+                rugged_at TEXT,
+                settled INTEGER NOT NULL DEFAULT 0
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS creators (
+                address TEXT PRIMARY KEY,
+                launches INTEGER NOT NULL,
+                rugs INTEGER NOT NULL,
+                average_peak_multiple REAL NOT NULL,
+                average_sold_share REAL NOT NULL,
+                average_secs_to_rug REAL,
+                score REAL NOT NULL,
+                blacklisted INTEGER NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS discovered_traders (
+                address TEXT PRIMARY KEY,
+                discovered_at TEXT NOT NULL,
+                last_seen_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS risk_state (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#
+        ).execute(&self.pool).await?;
+        
+        self.migrate_columns().await?;
+        
         // Create indexes for better performance
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_token_mint ON trades(token_mint)").execute(&self.pool).await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_timestamp ON trades(timestamp)").execute(&self.pool).await?;
@@ -150,14 +349,82 @@ impl Database {
         
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_launches_token_mint ON token_launches(token_mint)").execute(&self.pool).await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_launches_launch_time ON token_launches(launch_time)").execute(&self.pool).await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_creator_launches_creator ON creator_launches(creator_address)").execute(&self.pool).await?;
         
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_copy_trades_trader ON copy_trades(trader_address)").execute(&self.pool).await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_copy_trades_status ON copy_trades(status)").execute(&self.pool).await?;
         
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_positions_status ON positions(status)").execute(&self.pool).await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_positions_token_mint ON positions(token_mint)").execute(&self.pool).await?;
+        
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_lots_book ON lots(token_mint, strategy, status)").execute(&self.pool).await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_lot_closures_closed_at ON lot_closures(closed_at)").execute(&self.pool).await?;
+        
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_seen_trader_trades_time ON seen_trader_trades(trader_address, trade_time)").execute(&self.pool).await?;
+        
         info!("Database schema initialized successfully");
         Ok(())
     }
     
+    /// Add columns introduced after a table was first created. Each step is
+    /// idempotent so it is safe to run on every startup.
+    async fn migrate_columns(&self) -> Result<(), BotError> {
+        for column in ["fee_sol", "sol_spent", "sol_received"] {
+            self.add_column_if_missing("trades", column, "REAL NOT NULL DEFAULT 0").await?;
+        }
+        self.add_column_if_missing("copy_trades", "fee_sol", "REAL NOT NULL DEFAULT 0").await?;
+        
+        let execution_columns = [
+            ("leader_signature", "TEXT"),
+            ("leader_slot", "INTEGER"),
+            ("leader_price", "REAL NOT NULL DEFAULT 0"),
+            ("detected_at", "TEXT"),
+            ("sent_at", "TEXT"),
+            ("landed_slot", "INTEGER"),
+            ("fill_price", "REAL NOT NULL DEFAULT 0"),
+        ];
+        for (column, definition) in execution_columns {
+            self.add_column_if_missing("copy_trades", column, definition).await?;
+        }
+        
+        let exit_columns = [
+            ("initial_token_amount", "REAL NOT NULL DEFAULT 0"),
+            ("high_water_price", "REAL NOT NULL DEFAULT 0"),
+            ("take_profits_hit", "INTEGER NOT NULL DEFAULT 0"),
+        ];
+        let mut added_exit_columns = false;
+        for (column, definition) in exit_columns {
+            added_exit_columns |= self.add_column_if_missing("positions", column, definition).await?;
+        }
+        if added_exit_columns {
+            // Positions opened before the exit engine start from their current size and entry
+            sqlx::query("UPDATE positions SET initial_token_amount = token_amount WHERE initial_token_amount = 0")
+                .execute(&self.pool).await?;
+            sqlx::query("UPDATE positions SET high_water_price = MAX(entry_price, current_price) WHERE high_water_price = 0")
+                .execute(&self.pool).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Returns whether the column was added.
+    async fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<bool, BotError> {
+        let exists = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .any(|row| row.get::<String, _>("name") == column);
+        if exists {
+            return Ok(false);
+        }
+        
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(&self.pool)
+            .await?;
+        info!("Added column {}.{}", table, column);
+        Ok(true)
+    }
+    
     // Trade operations
     pub async fn record_trade(&self, trade: &Trade) -> Result<(), BotError> {
         let now = Utc::now();
@@ -167,8 +434,8 @@ impl Database {
             INSERT OR REPLACE INTO trades (
                 id, token_mint, trade_type, amount_sol, token_amount, price,
                 slippage, strategy, timestamp, status, transaction_signature,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                fee_sol, sol_spent, sol_received, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&trade.id)
@@ -178,209 +445,1098 @@ impl Database {
         .bind(trade.token_amount)
         .bind(trade.price)
         .bind(trade.slippage)
-        .bind(format!("{:?}", trade.strategy))
+        .bind(trade.strategy.name())
         .bind(trade.timestamp.to_rfc3339())
         .bind(&trade.status)
         .bind(&trade.transaction_signature)
+        .bind(trade.fee_sol)
+        .bind(trade.sol_spent)
+        .bind(trade.sol_received)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        info!("Trade recorded: {}", trade.id);
+        Ok(())
+    }
+    
+    pub async fn get_trade(&self, trade_id: &str) -> Result<Option<Trade>, BotError> {
+        let row = sqlx::query(
+            "SELECT * FROM trades WHERE id = ?"
+        )
+        .bind(trade_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        if let Some(row) = row {
+            Ok(Some(self.row_to_trade(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    pub async fn get_trades_by_token(&self, token_mint: &str, limit: Option<u32>) -> Result<Vec<Trade>, BotError> {
+        let limit = limit.unwrap_or(100);
+        
+        let rows = sqlx::query(
+            "SELECT * FROM trades WHERE token_mint = ? ORDER BY timestamp DESC LIMIT ?"
+        )
+        .bind(token_mint)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut trades = Vec::new();
+        for row in rows {
+            trades.push(self.row_to_trade(&row)?);
+        }
+        
+        Ok(trades)
+    }
+    
+    pub async fn get_total_trades(&self) -> Result<u64, BotError> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM trades").fetch_one(&self.pool).await?;
+        Ok(row.get::<i64, _>("count") as u64)
+    }
+    
+    /// Buys across the bot and copy trader since `since`, failed ones excluded.
+    pub async fn count_buys_since(&self, since: DateTime<Utc>) -> Result<u64, BotError> {
+        let since = since.to_rfc3339();
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM trades WHERE trade_type = 'buy' AND status != 'failed' AND timestamp >= ?) +
+                (SELECT COUNT(*) FROM copy_trades WHERE trade_type = 'buy' AND status != 'failed' AND timestamp >= ?)
+                as buys
+            "#
+        )
+        .bind(&since)
+        .bind(&since)
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(row.get::<i64, _>("buys") as u64)
+    }
+    
+    /// Today's realized ledger PnL plus unrealized PnL on open lots, fees included.
+    pub async fn get_daily_pnl(&self) -> Result<f64, BotError> {
+        let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+        
+        let pnl = self.get_pnl_breakdown(PnlGroup::Day).await?
+            .into_iter()
+            .find(|day| day.key == today)
+            .map(|day| day.realized_pnl + day.unrealized_pnl)
+            .unwrap_or(0.0);
+        
+        Ok(pnl)
+    }
+    
+    // Token launch operations
+    pub async fn record_token_launch(&self, launch: &TokenLaunch) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO token_launches (
+                id, token_mint, token_name, token_symbol, launch_time,
+                initial_price, price, market_cap, liquidity_sol, volume_24h,
+                token_type, has_flywheel, flywheel_activity, creator_address,
+                social_links, description, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&launch.token_mint)
+        .bind(&launch.token_mint)
+        .bind(&launch.token_name)
+        .bind(&launch.token_symbol)
+        .bind(launch.launch_time.to_rfc3339())
+        .bind(launch.initial_price)
+        .bind(launch.price)
+        .bind(launch.market_cap)
+        .bind(launch.liquidity_sol)
+        .bind(launch.volume_24h)
+        .bind(&launch.token_type)
+        .bind(launch.has_flywheel)
+        .bind(launch.flywheel_activity)
+        .bind(&launch.creator_address)
+        .bind(serde_json::to_string(&launch.social_links)?)
+        .bind(&launch.description)
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
         
-        info!("Trade recorded: {}", trade.id);
+        info!("Token launch recorded: {}", launch.token_mint);
+        Ok(())
+    }
+    
+    pub async fn get_recent_launches(&self, limit: Option<u32>) -> Result<Vec<TokenLaunch>, BotError> {
+        let limit = limit.unwrap_or(50);
+        
+        let rows = sqlx::query(
+            "SELECT * FROM token_launches ORDER BY launch_time DESC LIMIT ?"
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut launches = Vec::new();
+        for row in rows {
+            launches.push(self.row_to_token_launch(&row)?);
+        }
+        
+        Ok(launches)
+    }
+    
+    pub async fn get_token_launch(&self, token_mint: &str) -> Result<Option<TokenLaunch>, BotError> {
+        let row = sqlx::query(
+            "SELECT * FROM token_launches WHERE token_mint = ?"
+        )
+        .bind(token_mint)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        if let Some(row) = row {
+            Ok(Some(self.row_to_token_launch(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    // Snipe target operations
+    pub async fn record_snipe_target(&self, target: &SnipeTarget) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO snipe_targets (
+                token_mint, strategy, target_price, max_amount, priority,
+                conditions, is_active, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&target.token_mint)
+        .bind(target.strategy.name())
+        .bind(target.target_price)
+        .bind(target.max_amount)
+        .bind(format!("{:?}", target.priority))
+        .bind(serde_json::to_string(&target.conditions)?)
+        .bind(true)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        info!("Snipe target recorded: {}", target.token_mint);
+        Ok(())
+    }
+    
+    pub async fn get_active_snipe_targets(&self) -> Result<Vec<SnipeTarget>, BotError> {
+        let rows = sqlx::query(
+            "SELECT * FROM snipe_targets WHERE is_active = 1"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut targets = Vec::new();
+        for row in rows {
+            targets.push(self.row_to_snipe_target(&row)?);
+        }
+        
+        Ok(targets)
+    }
+    
+    /// Mark a target as fired. Targets from the config have no row yet, so one
+    /// is written to keep them off after a restart.
+    pub async fn deactivate_snipe_target(&self, target: &SnipeTarget) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT INTO snipe_targets (
+                token_mint, strategy, target_price, max_amount, priority,
+                conditions, is_active, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, 0, ?, ?)
+            ON CONFLICT(token_mint) DO UPDATE SET
+                is_active = 0,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&target.token_mint)
+        .bind(target.strategy.name())
+        .bind(target.target_price)
+        .bind(target.max_amount)
+        .bind(format!("{:?}", target.priority))
+        .bind(serde_json::to_string(&target.conditions)?)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn get_inactive_snipe_target_mints(&self) -> Result<Vec<String>, BotError> {
+        let rows = sqlx::query("SELECT token_mint FROM snipe_targets WHERE is_active = 0")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows.iter().map(|row| row.get("token_mint")).collect())
+    }
+    
+    // Copy trade operations
+    pub async fn record_copy_trade(&self, copy_trade: &CopyTrade) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO copy_trades (
+                id, original_trade_id, trader_address, trader_name, token_mint,
+                trade_type, amount_sol, token_amount, price, slippage,
+                timestamp, status, transaction_signature, fee_sol,
+                leader_signature, leader_slot, leader_price, detected_at, sent_at,
+                landed_slot, fill_price, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&copy_trade.id)
+        .bind(&copy_trade.original_trade_id)
+        .bind(&copy_trade.trader_address)
+        .bind(&copy_trade.trader_name)
+        .bind(&copy_trade.token_mint)
+        .bind(&copy_trade.trade_type)
+        .bind(copy_trade.amount_sol)
+        .bind(copy_trade.token_amount)
+        .bind(copy_trade.price)
+        .bind(copy_trade.slippage)
+        .bind(copy_trade.timestamp.to_rfc3339())
+        .bind(&copy_trade.status)
+        .bind(&copy_trade.transaction_signature)
+        .bind(copy_trade.fee_sol)
+        .bind(&copy_trade.execution.leader_signature)
+        .bind(copy_trade.execution.leader_slot.map(|slot| slot as i64))
+        .bind(copy_trade.execution.leader_price)
+        .bind(copy_trade.execution.detected_at.map(|t| t.to_rfc3339()))
+        .bind(copy_trade.execution.sent_at.map(|t| t.to_rfc3339()))
+        .bind(copy_trade.execution.landed_slot.map(|slot| slot as i64))
+        .bind(copy_trade.execution.fill_price)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        info!("Copy trade recorded: {}", copy_trade.id);
+        Ok(())
+    }
+    
+    pub async fn get_copy_trade(&self, copy_trade_id: &str) -> Result<Option<CopyTrade>, BotError> {
+        let row = sqlx::query(
+            "SELECT * FROM copy_trades WHERE id = ?"
+        )
+        .bind(copy_trade_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        if let Some(row) = row {
+            Ok(Some(self.row_to_copy_trade(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    /// Executed copies that can be compared with their leader's fill,
+    /// grouped by trader or token.
+    pub async fn get_copy_slippage_report(&self, group: CopySlippageGroup) -> Result<Vec<CopySlippageBreakdown>, BotError> {
+        let rows = sqlx::query(
+            "SELECT * FROM copy_trades WHERE leader_price > 0 AND fill_price > 0 AND transaction_signature IS NOT NULL"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        #[derive(Default)]
+        struct Sums {
+            copies: u64,
+            slippage: f64,
+            worst: Option<f64>,
+            cost_sol: f64,
+            reaction_ms: f64,
+            reactions: u64,
+            slot_lag: f64,
+            lags: u64,
+        }
+        
+        let mut sums: BTreeMap<String, Sums> = BTreeMap::new();
+        for row in rows {
+            let copy_trade = self.row_to_copy_trade(&row)?;
+            let execution = &copy_trade.execution;
+            let Some(slippage) = execution.slippage_vs_leader(&copy_trade.trade_type) else {
+                continue;
+            };
+            
+            let key = match group {
+                CopySlippageGroup::Trader => copy_trade.trader_address.clone(),
+                CopySlippageGroup::Token => copy_trade.token_mint.clone(),
+            };
+            let entry = sums.entry(key).or_default();
+            entry.copies += 1;
+            entry.slippage += slippage;
+            entry.worst = Some(entry.worst.map_or(slippage, |worst| worst.max(slippage)));
+            entry.cost_sol += copy_trade.token_amount * execution.leader_price * slippage;
+            if let (Some(detected), Some(sent)) = (execution.detected_at, execution.sent_at) {
+                entry.reaction_ms += (sent - detected).num_milliseconds() as f64;
+                entry.reactions += 1;
+            }
+            if let Some(lag) = execution.slot_lag() {
+                entry.slot_lag += lag as f64;
+                entry.lags += 1;
+            }
+        }
+        
+        Ok(sums.into_iter()
+            .map(|(key, sums)| CopySlippageBreakdown {
+                key,
+                copies: sums.copies,
+                average_slippage: sums.slippage / sums.copies as f64,
+                worst_slippage: sums.worst.unwrap_or(0.0),
+                slippage_cost_sol: sums.cost_sol,
+                average_reaction_ms: if sums.reactions > 0 { sums.reaction_ms / sums.reactions as f64 } else { 0.0 },
+                average_slot_lag: if sums.lags > 0 { sums.slot_lag / sums.lags as f64 } else { 0.0 },
+            })
+            .collect())
+    }
+    
+    // Position operations
+    pub async fn record_position(&self, position: &Position) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        // created_at is preserved across updates of the same position
+        sqlx::query(
+            r#"
+            INSERT INTO positions (
+                id, token_mint, source, trader_address, strategy, entry_price,
+                current_price, amount_sol, token_amount, unrealized_pnl, realized_pnl,
+                stop_loss, take_profit, status, initial_token_amount, high_water_price,
+                take_profits_hit, entry_time, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                current_price = excluded.current_price,
+                amount_sol = excluded.amount_sol,
+                token_amount = excluded.token_amount,
+                unrealized_pnl = excluded.unrealized_pnl,
+                realized_pnl = excluded.realized_pnl,
+                stop_loss = excluded.stop_loss,
+                take_profit = excluded.take_profit,
+                status = excluded.status,
+                initial_token_amount = excluded.initial_token_amount,
+                high_water_price = excluded.high_water_price,
+                take_profits_hit = excluded.take_profits_hit,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&position.id)
+        .bind(&position.token_mint)
+        .bind(&position.source)
+        .bind(&position.trader_address)
+        .bind(position.strategy.name())
+        .bind(position.entry_price)
+        .bind(position.current_price)
+        .bind(position.amount_sol)
+        .bind(position.token_amount)
+        .bind(position.unrealized_pnl)
+        .bind(position.realized_pnl)
+        .bind(position.stop_loss)
+        .bind(position.take_profit)
+        .bind(&position.status)
+        .bind(position.initial_token_amount)
+        .bind(position.high_water_price)
+        .bind(position.take_profits_hit as i64)
+        .bind(position.entry_time.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn get_open_positions(&self) -> Result<Vec<Position>, BotError> {
+        let rows = sqlx::query(
+            "SELECT * FROM positions WHERE status = 'open' ORDER BY entry_time ASC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut positions = Vec::new();
+        for row in rows {
+            positions.push(self.row_to_position(&row)?);
+        }
+        
+        Ok(positions)
+    }
+    
+    // Ledger operations
+    pub async fn record_lot(&self, lot: &Lot) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT INTO lots (
+                id, token_mint, source, strategy, trader_address, token_amount,
+                remaining_tokens, cost_sol, remaining_cost_sol, fee_sol, entry_time,
+                status, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                remaining_tokens = excluded.remaining_tokens,
+                remaining_cost_sol = excluded.remaining_cost_sol,
+                status = excluded.status,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&lot.id)
+        .bind(&lot.token_mint)
+        .bind(&lot.source)
+        .bind(lot.strategy.name())
+        .bind(&lot.trader_address)
+        .bind(lot.token_amount)
+        .bind(lot.remaining_tokens)
+        .bind(lot.cost_sol)
+        .bind(lot.remaining_cost_sol)
+        .bind(lot.fee_sol)
+        .bind(lot.entry_time.to_rfc3339())
+        .bind(&lot.status)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Open lots in one book (token, strategy and copied trader), oldest first.
+    pub async fn get_open_lots(
+        &self,
+        token_mint: &str,
+        strategy: &SniperStrategy,
+        trader_address: Option<&str>,
+    ) -> Result<Vec<Lot>, BotError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM lots
+            WHERE token_mint = ? AND strategy = ? AND trader_address IS ? AND status = 'open'
+            ORDER BY entry_time ASC
+            "#
+        )
+        .bind(token_mint)
+        .bind(strategy.name())
+        .bind(trader_address)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut lots = Vec::new();
+        for row in rows {
+            lots.push(self.row_to_lot(&row)?);
+        }
+        
+        Ok(lots)
+    }
+    
+    pub async fn record_lot_closure(&self, closure: &LotClosure) -> Result<(), BotError> {
+        sqlx::query(
+            r#"
+            INSERT INTO lot_closures (
+                id, lot_id, token_mint, strategy, trader_address, tokens, cost_sol,
+                proceeds_sol, fee_sol, realized_pnl, closed_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&closure.id)
+        .bind(&closure.lot_id)
+        .bind(&closure.token_mint)
+        .bind(closure.strategy.name())
+        .bind(&closure.trader_address)
+        .bind(closure.tokens)
+        .bind(closure.cost_sol)
+        .bind(closure.proceeds_sol)
+        .bind(closure.fee_sol)
+        .bind(closure.realized_pnl)
+        .bind(closure.closed_at.to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Realized PnL from lot closures plus unrealized PnL of open lots,
+    /// marked at the latest position price for their token. When grouping by
+    /// day, unrealized PnL is reported under today.
+    pub async fn get_pnl_breakdown(&self, group: PnlGroup) -> Result<Vec<PnlBreakdown>, BotError> {
+        let (closure_key, lot_key) = match group {
+            PnlGroup::Token => ("token_mint", "token_mint"),
+            PnlGroup::Strategy => ("strategy", "strategy"),
+            PnlGroup::Trader => ("trader_address", "trader_address"),
+            PnlGroup::Day => ("DATE(closed_at)", "DATE('now')"),
+        };
+        let trader_filter = if group == PnlGroup::Trader { "trader_address IS NOT NULL" } else { "1 = 1" };
+        
+        let realized_rows = sqlx::query(&format!(
+            r#"
+            SELECT {key} as key, SUM(realized_pnl) as realized, SUM(fee_sol) as fees, COUNT(*) as closures
+            FROM lot_closures
+            WHERE {filter}
+            GROUP BY key
+            "#,
+            key = closure_key,
+            filter = trader_filter,
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let unrealized_rows = sqlx::query(&format!(
+            r#"
+            SELECT {key} as key, SUM(unrealized) as unrealized, COUNT(*) as open_lots
+            FROM (
+                SELECT l.*, COALESCE(
+                    l.remaining_tokens * (
+                        SELECT p.current_price FROM positions p
+                        WHERE p.token_mint = l.token_mint
+                        ORDER BY p.updated_at DESC LIMIT 1
+                    ) - l.remaining_cost_sol,
+                    0
+                ) as unrealized
+                FROM lots l
+                WHERE l.status = 'open'
+            )
+            WHERE {filter}
+            GROUP BY key
+            "#,
+            key = lot_key,
+            filter = trader_filter,
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut breakdown: BTreeMap<String, PnlBreakdown> = BTreeMap::new();
+        let empty = |key: &str| PnlBreakdown {
+            key: key.to_string(),
+            realized_pnl: 0.0,
+            unrealized_pnl: 0.0,
+            fees_sol: 0.0,
+            closures: 0,
+            open_lots: 0,
+        };
+        
+        for row in realized_rows {
+            let key: String = row.get("key");
+            let entry = breakdown.entry(key.clone()).or_insert_with(|| empty(&key));
+            entry.realized_pnl = row.get::<Option<f64>, _>("realized").unwrap_or(0.0);
+            entry.fees_sol = row.get::<Option<f64>, _>("fees").unwrap_or(0.0);
+            entry.closures = row.get::<i64, _>("closures") as u64;
+        }
+        
+        for row in unrealized_rows {
+            let key: String = row.get("key");
+            let entry = breakdown.entry(key.clone()).or_insert_with(|| empty(&key));
+            entry.unrealized_pnl = row.get::<Option<f64>, _>("unrealized").unwrap_or(0.0);
+            entry.open_lots = row.get::<i64, _>("open_lots") as u64;
+        }
+        
+        Ok(breakdown.into_values().collect())
+    }
+    
+    /// Fully closed lots as round-trip trades, oldest first. With `since`,
+    /// only lots whose last closure is at or after it are returned.
+    pub async fn get_closed_trades(&self, since: Option<DateTime<Utc>>) -> Result<Vec<metrics::ClosedTrade>, BotError> {
+        let since = since.map(|t| t.to_rfc3339()).unwrap_or_default();
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT l.id, l.token_mint, l.strategy, l.trader_address, l.token_amount, l.cost_sol,
+                   SUM(c.proceeds_sol) as proceeds_sol, SUM(c.realized_pnl) as realized_pnl,
+                   SUM(c.fee_sol) as fee_sol, MAX(c.closed_at) as closed_at
+            FROM lots l
+            JOIN lot_closures c ON c.lot_id = l.id
+            WHERE l.status = 'closed'
+            GROUP BY l.id
+            HAVING MAX(c.closed_at) >= ?
+            ORDER BY closed_at ASC
+            "#
+        )
+        .bind(&since)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut trades = Vec::new();
+        for row in rows {
+            trades.push(metrics::ClosedTrade {
+                lot_id: row.get("id"),
+                token_mint: row.get("token_mint"),
+                strategy: Self::parse_strategy(row.get("strategy")),
+                trader_address: row.get("trader_address"),
+                token_amount: row.get("token_amount"),
+                cost_sol: row.get("cost_sol"),
+                proceeds_sol: row.get("proceeds_sol"),
+                realized_pnl: row.get("realized_pnl"),
+                fee_sol: row.get("fee_sol"),
+                closed_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("closed_at"))?.with_timezone(&Utc),
+            });
+        }
+        
+        Ok(trades)
+    }
+    
+    // Trader operations
+    pub async fn record_trader(&self, trader: &Trader) -> Result<(), BotError> {
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO traders (
+                address, name, total_trades, successful_trades, total_profit,
+                win_rate, average_profit, total_volume, last_trade_time,
+                is_verified, risk_score, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&trader.address)
+        .bind(&trader.name)
+        .bind(trader.total_trades)
+        .bind(trader.successful_trades)
+        .bind(trader.total_profit)
+        .bind(trader.win_rate)
+        .bind(trader.average_profit)
+        .bind(trader.total_volume)
+        .bind(trader.last_trade_time.to_rfc3339())
+        .bind(trader.is_verified)
+        .bind(trader.risk_score)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Claim a leader trade for copying. Returns false if it was already seen.
+    pub async fn mark_trader_trade_seen(
+        &self,
+        trader_address: &str,
+        signature: &str,
+        trade_time: DateTime<Utc>,
+    ) -> Result<bool, BotError> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO seen_trader_trades (
+                trader_address, signature, trade_time, seen_at
+            ) VALUES (?, ?, ?, ?)
+            "#
+        )
+        .bind(trader_address)
+        .bind(signature)
+        .bind(trade_time.to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(result.rows_affected() == 1)
+    }
+    
+    // Risk state operations
+    pub async fn get_risk_state(&self, key: &str) -> Result<Option<String>, BotError> {
+        let row = sqlx::query("SELECT value FROM risk_state WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(row.map(|row| row.get("value")))
+    }
+    
+    pub async fn set_risk_state(&self, key: &str, value: &str) -> Result<(), BotError> {
+        sqlx::query("INSERT OR REPLACE INTO risk_state (key, value, updated_at) VALUES (?, ?, ?)")
+            .bind(key)
+            .bind(value)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn clear_risk_state(&self, key: &str) -> Result<(), BotError> {
+        sqlx::query("DELETE FROM risk_state WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    /// Time of the newest trade seen from a leader.
+    pub async fn get_trader_watermark(&self, trader_address: &str) -> Result<Option<DateTime<Utc>>, BotError> {
+        let row = sqlx::query(
+            "SELECT last_trade_time FROM trader_watermarks WHERE trader_address = ?"
+        )
+        .bind(trader_address)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(row
+            .and_then(|row| DateTime::parse_from_rfc3339(&row.get::<String, _>("last_trade_time")).ok())
+            .map(|time| time.with_timezone(&Utc)))
+    }
+    
+    /// Move a leader's watermark forward; it never moves back.
+    pub async fn advance_trader_watermark(&self, trader_address: &str, trade_time: DateTime<Utc>) -> Result<(), BotError> {
+        let now = Utc::now().to_rfc3339();
+        
+        sqlx::query(
+            r#"
+            INSERT INTO trader_watermarks (trader_address, last_trade_time, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(trader_address) DO UPDATE SET
+                last_trade_time = MAX(last_trade_time, excluded.last_trade_time),
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(trader_address)
+        .bind(trade_time.to_rfc3339())
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Forget a leader's seen trades older than `before`; the watermark keeps them from being copied.
+    pub async fn prune_seen_trader_trades(&self, trader_address: &str, before: DateTime<Utc>) -> Result<u64, BotError> {
+        let result = sqlx::query("DELETE FROM seen_trader_trades WHERE trader_address = ? AND trade_time < ?")
+            .bind(trader_address)
+            .bind(before.to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected())
+    }
+    
+    pub async fn record_faded_wallet(&self, wallet: &FadedWallet) -> Result<(), BotError> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO faded_wallets (
+                address, realized_pnl, round_trips, win_rate, updated_at
+            ) VALUES (?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&wallet.address)
+        .bind(wallet.realized_pnl)
+        .bind(wallet.round_trips as i64)
+        .bind(wallet.win_rate)
+        .bind(wallet.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// Faded wallets, biggest losers first.
+    pub async fn get_faded_wallets(&self, limit: usize) -> Result<Vec<FadedWallet>, BotError> {
+        let rows = sqlx::query("SELECT * FROM faded_wallets ORDER BY realized_pnl ASC LIMIT ?")
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut wallets = Vec::new();
+        for row in rows {
+            wallets.push(FadedWallet {
+                address: row.get("address"),
+                realized_pnl: row.get("realized_pnl"),
+                round_trips: row.get::<i64, _>("round_trips") as u64,
+                win_rate: row.get("win_rate"),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))?.with_timezone(&Utc),
+            });
+        }
+        
+        Ok(wallets)
+    }
+    
+    /// Delete faded wallets discovery has not recorded since `before`.
+    pub async fn expire_faded_wallets(&self, before: DateTime<Utc>) -> Result<u64, BotError> {
+        let result = sqlx::query("DELETE FROM faded_wallets WHERE updated_at < ?")
+            .bind(before.to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected())
+    }
+    
+    pub async fn record_trader_status_change(&self, change: &TraderStatusChange) -> Result<(), BotError> {
+        sqlx::query(
+            r#"
+            INSERT INTO trader_status_history (
+                trader_address, from_status, to_status, reason, copies,
+                copy_return, win_rate, changed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&change.trader_address)
+        .bind(change.from_status.name())
+        .bind(change.to_status.name())
+        .bind(&change.reason)
+        .bind(change.copies as i64)
+        .bind(change.copy_return)
+        .bind(change.win_rate)
+        .bind(change.changed_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
         Ok(())
     }
     
-    pub async fn get_trade(&self, trade_id: &str) -> Result<Option<Trade>, BotError> {
-        let row = sqlx::query(
-            "SELECT * FROM trades WHERE id = ?"
+    /// Status changes, newest first, of one trader or of all of them.
+    pub async fn get_trader_status_history(
+        &self,
+        trader_address: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TraderStatusChange>, BotError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM trader_status_history
+            WHERE ? IS NULL OR trader_address = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#
         )
-        .bind(trade_id)
-        .fetch_optional(&self.pool)
+        .bind(trader_address)
+        .bind(trader_address)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
         .await?;
         
-        if let Some(row) = row {
-            Ok(Some(self.row_to_trade(&row)?))
-        } else {
-            Ok(None)
-        }
+        rows.iter().map(Self::row_to_trader_status_change).collect()
     }
     
-    pub async fn get_trades_by_token(&self, token_mint: &str, limit: Option<u32>) -> Result<Vec<Trade>, BotError> {
-        let limit = limit.unwrap_or(100);
-        
+    /// The latest status change of every trader that has one.
+    pub async fn get_latest_trader_statuses(&self) -> Result<Vec<TraderStatusChange>, BotError> {
         let rows = sqlx::query(
-            "SELECT * FROM trades WHERE token_mint = ? ORDER BY timestamp DESC LIMIT ?"
+            r#"
+            SELECT * FROM trader_status_history
+            WHERE id IN (SELECT MAX(id) FROM trader_status_history GROUP BY trader_address)
+            "#
         )
-        .bind(token_mint)
-        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
         
-        let mut trades = Vec::new();
-        for row in rows {
-            trades.push(self.row_to_trade(&row)?);
-        }
-        
-        Ok(trades)
+        rows.iter().map(Self::row_to_trader_status_change).collect()
     }
     
-    pub async fn get_total_trades(&self) -> Result<u64, BotError> {
-        let row = sqlx::query("SELECT COUNT(*) as count FROM trades").fetch_one(&self.pool).await?;
-        Ok(row.get::<i64, _>("count") as u64)
+    fn row_to_trader_status_change(row: &sqlx::sqlite::SqliteRow) -> Result<TraderStatusChange, BotError> {
+        Ok(TraderStatusChange {
+            trader_address: row.get("trader_address"),
+            from_status: TraderStatus::from_name(row.get("from_status")),
+            to_status: TraderStatus::from_name(row.get("to_status")),
+            reason: row.get("reason"),
+            copies: row.get::<i64, _>("copies") as u64,
+            copy_return: row.get("copy_return"),
+            win_rate: row.get("win_rate"),
+            changed_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("changed_at"))?.with_timezone(&Utc),
+        })
     }
     
-    pub async fn get_daily_pnl(&self) -> Result<f64, BotError> {
-        let today = Utc::now().date_naive();
-        let today_str = today.format("%Y-%m-%d").to_string();
-        
-        let row = sqlx::query(
-            "SELECT SUM(CASE WHEN trade_type = 'sell' THEN amount_sol ELSE -amount_sol END) as pnl FROM trades WHERE DATE(timestamp) = ?"
+    /// Start watching a creator's launch. Returns false if it is already recorded.
+    pub async fn record_creator_launch(&self, launch: &CreatorLaunch) -> Result<bool, BotError> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO creator_launches (
+                token_mint, creator_address, launch_time, initial_price, peak_price,
+                creator_initial_tokens, creator_sold_share, rugged_at, settled
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
         )
-        .bind(&today_str)
-        .fetch_one(&self.pool)
+        .bind(&launch.token_mint)
+        .bind(&launch.creator_address)
+        .bind(launch.launch_time.to_rfc3339())
+        .bind(launch.initial_price)
+        .bind(launch.peak_price)
+        .bind(launch.creator_initial_tokens)
+        .bind(launch.creator_sold_share)
+        .bind(launch.rugged_at.map(|t| t.to_rfc3339()))
+        .bind(launch.settled)
+        .execute(&self.pool)
         .await?;
         
-        Ok(row.get::<Option<f64>, _>("pnl").unwrap_or(0.0))
+        Ok(result.rows_affected() > 0)
     }
     
-    // Token launch operations
-    pub async fn record_token_launch(&self, launch: &TokenLaunch) -> Result<(), BotError> {
-        let now = Utc::now();
-        
+    pub async fn update_creator_launch(&self, launch: &CreatorLaunch) -> Result<(), BotError> {
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO token_launches (
-                id, token_mint, token_name, token_symbol, launch_time,
-                initial_price, price, market_cap, liquidity_sol, volume_24h,
-                token_type, has_flywheel, flywheel_activity, creator_address,
-                social_links, description, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            UPDATE creator_launches
+            SET peak_price = ?, creator_sold_share = ?, rugged_at = ?, settled = ?
+            WHERE token_mint = ?
             "#
         )
+        .bind(launch.peak_price)
+        .bind(launch.creator_sold_share)
+        .bind(launch.rugged_at.map(|t| t.to_rfc3339()))
+        .bind(launch.settled)
         .bind(&launch.token_mint)
-        .bind(&launch.token_mint)
-        .bind(&launch.token_name)
-        .bind(&launch.token_symbol)
-        .bind(launch.launch_time.to_rfc3339())
-        .bind(launch.initial_price)
-        .bind(launch.price)
-        .bind(launch.market_cap)
-        .bind(launch.liquidity_sol)
-        .bind(launch.volume_24h)
-        .bind(&launch.token_type)
-        .bind(launch.has_flywheel)
-        .bind(launch.flywheel_activity)
-        .bind(&launch.creator_address)
-        .bind(serde_json::to_string(&launch.social_links)?)
-        .bind(&launch.description)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
         
-        info!("Token launch recorded: {}", launch.token_mint);
         Ok(())
     }
     
-    pub async fn get_recent_launches(&self, limit: Option<u32>) -> Result<Vec<TokenLaunch>, BotError> {
-        let limit = limit.unwrap_or(50);
-        
-        let rows = sqlx::query(
-            "SELECT * FROM token_launches ORDER BY launch_time DESC LIMIT ?"
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-        
-        let mut launches = Vec::new();
-        for row in rows {
-            launches.push(self.row_to_token_launch(&row)?);
-        }
+    /// Creator launches still being watched.
+    pub async fn get_watched_creator_launches(&self) -> Result<Vec<CreatorLaunch>, BotError> {
+        let rows = sqlx::query("SELECT * FROM creator_launches WHERE settled = 0")
+            .fetch_all(&self.pool)
+            .await?;
         
-        Ok(launches)
+        rows.iter().map(Self::row_to_creator_launch).collect()
     }
     
-    // Copy trade operations
-    pub async fn record_copy_trade(&self, copy_trade: &CopyTrade) -> Result<(), BotError> {
-        let now = Utc::now();
+    pub async fn get_creator_launches(&self, creator_address: &str) -> Result<Vec<CreatorLaunch>, BotError> {
+        let rows = sqlx::query("SELECT * FROM creator_launches WHERE creator_address = ? ORDER BY launch_time ASC")
+            .bind(creator_address)
+            .fetch_all(&self.pool)
+            .await?;
         
+        rows.iter().map(Self::row_to_creator_launch).collect()
+    }
+    
+    fn row_to_creator_launch(row: &sqlx::sqlite::SqliteRow) -> Result<CreatorLaunch, BotError> {
+        Ok(CreatorLaunch {
+            token_mint: row.get("token_mint"),
+            creator_address: row.get("creator_address"),
+            launch_time: DateTime::parse_from_rfc3339(&row.get::<String, _>("launch_time"))?.with_timezone(&Utc),
+            initial_price: row.get("initial_price"),
+            peak_price: row.get("peak_price"),
+            creator_initial_tokens: row.get("creator_initial_tokens"),
+            creator_sold_share: row.get("creator_sold_share"),
+            rugged_at: Self::parse_optional_time(row.get("rugged_at"))?,
+            settled: row.get("settled"),
+        })
+    }
+    
+    pub async fn record_creator_reputation(&self, reputation: &CreatorReputation) -> Result<(), BotError> {
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO copy_trades (
-                id, original_trade_id, trader_address, trader_name, token_mint,
-                trade_type, amount_sol, token_amount, price, slippage,
-                timestamp, status, transaction_signature, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT OR REPLACE INTO creators (
+                address, launches, rugs, average_peak_multiple, average_sold_share,
+                average_secs_to_rug, score, blacklisted, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
-        .bind(&copy_trade.id)
-        .bind(&copy_trade.original_trade_id)
-        .bind(&copy_trade.trader_address)
-        .bind(&copy_trade.trader_name)
-        .bind(&copy_trade.token_mint)
-        .bind(&copy_trade.trade_type)
-        .bind(copy_trade.amount_sol)
-        .bind(copy_trade.token_amount)
-        .bind(copy_trade.price)
-        .bind(copy_trade.slippage)
-        .bind(copy_trade.timestamp.to_rfc3339())
-        .bind(&copy_trade.status)
-        .bind(&copy_trade.transaction_signature)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
+        .bind(&reputation.address)
+        .bind(reputation.launches as i64)
+        .bind(reputation.rugs as i64)
+        .bind(reputation.average_peak_multiple)
+        .bind(reputation.average_sold_share)
+        .bind(reputation.average_secs_to_rug)
+        .bind(reputation.score)
+        .bind(reputation.blacklisted)
+        .bind(reputation.updated_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
         
-        info!("Copy trade recorded: {}", copy_trade.id);
         Ok(())
     }
     
-    // Trader operations
-    pub async fn record_trader(&self, trader: &Trader) -> Result<(), BotError> {
-        let now = Utc::now();
+    pub async fn get_creator_reputations(&self) -> Result<Vec<CreatorReputation>, BotError> {
+        let rows = sqlx::query("SELECT * FROM creators")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut reputations = Vec::new();
+        for row in rows {
+            reputations.push(CreatorReputation {
+                address: row.get("address"),
+                launches: row.get::<i64, _>("launches") as u64,
+                rugs: row.get::<i64, _>("rugs") as u64,
+                average_peak_multiple: row.get("average_peak_multiple"),
+                average_sold_share: row.get("average_sold_share"),
+                average_secs_to_rug: row.get("average_secs_to_rug"),
+                score: row.get("score"),
+                blacklisted: row.get("blacklisted"),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))?.with_timezone(&Utc),
+            });
+        }
+        
+        Ok(reputations)
+    }
+    
+    pub async fn get_tracked_traders(&self) -> Result<Vec<Trader>, BotError> {
+        let rows = sqlx::query(
+            "SELECT * FROM traders ORDER BY total_profit DESC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut traders = Vec::new();
+        for row in rows {
+            traders.push(self.row_to_trader(&row)?);
+        }
+        
+        Ok(traders)
+    }
+    
+    /// Note that discovery found `address` qualifying. Only traders discovery
+    /// added itself (`is_new`) are tracked for expiry; ones added by hand are left alone.
+    pub async fn record_discovered_trader(&self, address: &str, is_new: bool) -> Result<(), BotError> {
+        let now = Utc::now().to_rfc3339();
+        
+        if !is_new {
+            sqlx::query("UPDATE discovered_traders SET last_seen_at = ? WHERE address = ?")
+                .bind(&now)
+                .bind(address)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
         
         sqlx::query(
             r#"
-            INSERT OR REPLACE INTO traders (
-                address, name, total_trades, successful_trades, total_profit,
-                win_rate, average_profit, total_volume, last_trade_time,
-                is_verified, risk_score, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO discovered_traders (address, discovered_at, last_seen_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(address) DO UPDATE SET last_seen_at = excluded.last_seen_at
             "#
         )
-        .bind(&trader.address)
-        .bind(&trader.name)
-        .bind(trader.total_trades)
-        .bind(trader.successful_trades)
-        .bind(trader.total_profit)
-        .bind(trader.win_rate)
-        .bind(trader.average_profit)
-        .bind(trader.total_volume)
-        .bind(trader.last_trade_time.to_rfc3339())
-        .bind(trader.is_verified)
-        .bind(trader.risk_score)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
+        .bind(address)
+        .bind(&now)
+        .bind(&now)
         .execute(&self.pool)
         .await?;
         
         Ok(())
     }
     
-    pub async fn get_tracked_traders(&self) -> Result<Vec<Trader>, BotError> {
+    /// Remove discovered traders not seen qualifying since `before`. Traders
+    /// verified by hand are kept. Returns the removed addresses.
+    pub async fn expire_discovered_traders(&self, before: DateTime<Utc>) -> Result<Vec<String>, BotError> {
         let rows = sqlx::query(
-            "SELECT * FROM traders ORDER BY total_profit DESC"
+            r#"
+            SELECT d.address FROM discovered_traders d
+            JOIN traders t ON t.address = d.address
+            WHERE d.last_seen_at < ? AND t.is_verified = 0
+            "#
         )
+        .bind(before.to_rfc3339())
         .fetch_all(&self.pool)
         .await?;
         
-        let mut traders = Vec::new();
-        for row in rows {
-            traders.push(self.row_to_trader(&row)?);
+        let addresses: Vec<String> = rows.iter().map(|row| row.get("address")).collect();
+        for address in &addresses {
+            sqlx::query("DELETE FROM traders WHERE address = ?")
+                .bind(address)
+                .execute(&self.pool)
+                .await?;
+            sqlx::query("DELETE FROM discovered_traders WHERE address = ?")
+                .bind(address)
+                .execute(&self.pool)
+                .await?;
         }
         
-        Ok(traders)
+        Ok(addresses)
     }
     
     pub async fn get_trader(&self, address: &str) -> Result<Option<Trader>, BotError> {
@@ -454,15 +1610,7 @@ impl Database {
     
     // Helper methods to convert database rows to types
     fn row_to_trade(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Trade, BotError> {
-        let strategy_str: String = row.get("strategy");
-        let strategy = match strategy_str.as_str() {
-            "CreatorToken" => crate::types::SniperStrategy::CreatorToken,
-            "CommunityToken" => crate::types::SniperStrategy::CommunityToken,
-            "HighVolume" => crate::types::SniperStrategy::HighVolume,
-            "LowMarketCap" => crate::types::SniperStrategy::LowMarketCap,
-            "FlywheelActive" => crate::types::SniperStrategy::FlywheelActive,
-            _ => crate::types::SniperStrategy::Custom(strategy_str),
-        };
+        let strategy = Self::parse_strategy(row.get("strategy"));
         
         Ok(Trade {
             id: row.get("id"),
@@ -476,9 +1624,119 @@ impl Database {
             timestamp: DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))?.with_timezone(&Utc),
             status: row.get("status"),
             transaction_signature: row.get("transaction_signature"),
+            fee_sol: row.get("fee_sol"),
+            sol_spent: row.get("sol_spent"),
+            sol_received: row.get("sol_received"),
+            slot: None,
+            token_balance_before: None,
+        })
+    }
+    
+    fn row_to_copy_trade(&self, row: &sqlx::sqlite::SqliteRow) -> Result<CopyTrade, BotError> {
+        Ok(CopyTrade {
+            id: row.get("id"),
+            original_trade_id: row.get("original_trade_id"),
+            trader_address: row.get("trader_address"),
+            trader_name: row.get("trader_name"),
+            token_mint: row.get("token_mint"),
+            trade_type: row.get("trade_type"),
+            amount_sol: row.get("amount_sol"),
+            token_amount: row.get("token_amount"),
+            price: row.get("price"),
+            slippage: row.get("slippage"),
+            timestamp: DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))?.with_timezone(&Utc),
+            status: row.get("status"),
+            transaction_signature: row.get("transaction_signature"),
+            fee_sol: row.get("fee_sol"),
+            execution: CopyExecution {
+                leader_signature: row.get("leader_signature"),
+                leader_slot: row.get::<Option<i64>, _>("leader_slot").map(|slot| slot as u64),
+                leader_price: row.get("leader_price"),
+                detected_at: Self::parse_optional_time(row.get("detected_at"))?,
+                sent_at: Self::parse_optional_time(row.get("sent_at"))?,
+                landed_slot: row.get::<Option<i64>, _>("landed_slot").map(|slot| slot as u64),
+                fill_price: row.get("fill_price"),
+            },
+        })
+    }
+    
+    fn parse_optional_time(value: Option<String>) -> Result<Option<DateTime<Utc>>, BotError> {
+        match value {
+            Some(value) => Ok(Some(DateTime::parse_from_rfc3339(&value)?.with_timezone(&Utc))),
+            None => Ok(None),
+        }
+    }
+    
+    fn row_to_position(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Position, BotError> {
+        Ok(Position {
+            id: row.get("id"),
+            token_mint: row.get("token_mint"),
+            entry_price: row.get("entry_price"),
+            current_price: row.get("current_price"),
+            amount_sol: row.get("amount_sol"),
+            token_amount: row.get("token_amount"),
+            unrealized_pnl: row.get("unrealized_pnl"),
+            entry_time: DateTime::parse_from_rfc3339(&row.get::<String, _>("entry_time"))?.with_timezone(&Utc),
+            strategy: Self::parse_strategy(row.get("strategy")),
+            stop_loss: row.get("stop_loss"),
+            take_profit: row.get("take_profit"),
+            source: row.get("source"),
+            trader_address: row.get("trader_address"),
+            realized_pnl: row.get("realized_pnl"),
+            status: row.get("status"),
+            initial_token_amount: row.get("initial_token_amount"),
+            high_water_price: row.get("high_water_price"),
+            take_profits_hit: row.get::<i64, _>("take_profits_hit") as u32,
         })
     }
     
+    fn row_to_lot(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Lot, BotError> {
+        Ok(Lot {
+            id: row.get("id"),
+            token_mint: row.get("token_mint"),
+            source: row.get("source"),
+            strategy: Self::parse_strategy(row.get("strategy")),
+            trader_address: row.get("trader_address"),
+            token_amount: row.get("token_amount"),
+            remaining_tokens: row.get("remaining_tokens"),
+            cost_sol: row.get("cost_sol"),
+            remaining_cost_sol: row.get("remaining_cost_sol"),
+            fee_sol: row.get("fee_sol"),
+            entry_time: DateTime::parse_from_rfc3339(&row.get::<String, _>("entry_time"))?.with_timezone(&Utc),
+            status: row.get("status"),
+        })
+    }
+    
+    fn row_to_snipe_target(&self, row: &sqlx::sqlite::SqliteRow) -> Result<SnipeTarget, BotError> {
+        let conditions: String = row.get("conditions");
+        let priority_str: String = row.get("priority");
+        let priority = match priority_str.as_str() {
+            "Low" => SnipePriority::Low,
+            "Medium" => SnipePriority::Medium,
+            "High" => SnipePriority::High,
+            "Critical" => SnipePriority::Critical,
+            _ => return Err(BotError::Database(format!("Unknown snipe priority: {}", priority_str))),
+        };
+        
+        Ok(SnipeTarget {
+            token_mint: row.get("token_mint"),
+            strategy: Self::parse_strategy(row.get("strategy")),
+            target_price: row.get("target_price"),
+            max_amount: row.get("max_amount"),
+            priority,
+            conditions: serde_json::from_str(&conditions)?,
+        })
+    }
+    
+    fn parse_strategy(strategy_str: String) -> SniperStrategy {
+        // Rows written before strategies were stored by name use the Debug form
+        let name = strategy_str
+            .strip_prefix("Custom(\"")
+            .and_then(|rest| rest.strip_suffix("\")"))
+            .unwrap_or(&strategy_str);
+        SniperStrategy::from_name(name)
+    }
+    
     fn row_to_token_launch(&self, row: &sqlx::sqlite::SqliteRow) -> Result<TokenLaunch, BotError> {
         let social_links: String = row.get("social_links");
         let social_links: Vec<String> = serde_json::from_str(&social_links)?;
@@ -567,3 +1825,251 @@ pub struct DatabaseStats {
     pub traders_count: u64,
     pub bundles_count: u64,
 }
+
+/// Performance statistics built from closed ledger lots. Each fully closed
+/// lot counts as one trade; partial exits are summed into it.
+pub mod metrics {
+    use super::Database;
+    use crate::{
+        error::BotError,
+        types::{DailyStats, EquityPoint, PerformanceMetrics, SniperStrategy, Trade},
+    };
+    use chrono::{DateTime, NaiveDate, Utc};
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    
+    #[derive(Debug, Clone)]
+    pub struct ClosedTrade {
+        pub lot_id: String,
+        pub token_mint: String,
+        pub strategy: SniperStrategy,
+        pub trader_address: Option<String>,
+        pub token_amount: f64,
+        pub cost_sol: f64,
+        pub proceeds_sol: f64,
+        pub realized_pnl: f64,
+        pub fee_sol: f64,
+        pub closed_at: DateTime<Utc>,
+    }
+    
+    impl ClosedTrade {
+        fn return_pct(&self) -> f64 {
+            if self.cost_sol > 0.0 { self.realized_pnl / self.cost_sol } else { 0.0 }
+        }
+        
+        // Round trip as a single sell-side trade for best/worst reporting
+        fn to_trade(&self) -> Trade {
+            Trade {
+                id: self.lot_id.clone(),
+                token_mint: self.token_mint.clone(),
+                trade_type: "sell".to_string(),
+                amount_sol: self.proceeds_sol,
+                token_amount: self.token_amount,
+                price: if self.token_amount > 0.0 { self.proceeds_sol / self.token_amount } else { 0.0 },
+                slippage: 0.0,
+                strategy: self.strategy.clone(),
+                timestamp: self.closed_at,
+                status: "closed".to_string(),
+                transaction_signature: None,
+                fee_sol: self.fee_sol,
+                sol_spent: self.cost_sol,
+                sol_received: self.proceeds_sol,
+                slot: None,
+                token_balance_before: None,
+            }
+        }
+    }
+    
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum MetricsSlice {
+        All,
+        Strategy(String),
+        Trader(String),
+    }
+    
+    /// Running statistics for one slice; Sharpe and Sortino are per-trade
+    /// ratios over trade returns (PnL / cost) and are not annualized.
+    #[derive(Debug, Clone, Default)]
+    struct Accumulator {
+        total_trades: u64,
+        winning_trades: u64,
+        losing_trades: u64,
+        gross_profit: f64,
+        gross_loss: f64,
+        total_pnl: f64,
+        total_volume: f64,
+        total_fees: f64,
+        peak_equity: f64,
+        max_drawdown: f64,
+        mean_return: f64,
+        return_m2: f64,
+        downside_sq_sum: f64,
+        best_trade_pnl: Option<f64>,
+        worst_trade_pnl: Option<f64>,
+        equity_curve: Vec<EquityPoint>,
+        last_updated: Option<DateTime<Utc>>,
+    }
+    
+    impl Accumulator {
+        fn push(&mut self, trade: &ClosedTrade) {
+            let pnl = trade.realized_pnl;
+            
+            self.total_trades += 1;
+            if pnl > 0.0 {
+                self.winning_trades += 1;
+                self.gross_profit += pnl;
+            } else if pnl < 0.0 {
+                self.losing_trades += 1;
+                self.gross_loss += -pnl;
+            }
+            self.total_pnl += pnl;
+            self.total_volume += trade.cost_sol + trade.proceeds_sol;
+            self.total_fees += trade.fee_sol;
+            
+            // Equity is cumulative realized PnL; drawdown is measured in SOL from its peak
+            self.peak_equity = self.peak_equity.max(self.total_pnl);
+            let drawdown = self.peak_equity - self.total_pnl;
+            self.max_drawdown = self.max_drawdown.max(drawdown);
+            self.equity_curve.push(EquityPoint {
+                timestamp: trade.closed_at,
+                equity: self.total_pnl,
+                drawdown,
+            });
+            
+            // Welford's online mean and variance of returns
+            let r = trade.return_pct();
+            let delta = r - self.mean_return;
+            self.mean_return += delta / self.total_trades as f64;
+            self.return_m2 += delta * (r - self.mean_return);
+            if r < 0.0 {
+                self.downside_sq_sum += r * r;
+            }
+            
+            self.best_trade_pnl = Some(self.best_trade_pnl.map_or(pnl, |b| b.max(pnl)));
+            self.worst_trade_pnl = Some(self.worst_trade_pnl.map_or(pnl, |w| w.min(pnl)));
+            self.last_updated = Some(trade.closed_at);
+        }
+        
+        fn snapshot(&self) -> PerformanceMetrics {
+            let n = self.total_trades as f64;
+            
+            let std_dev = if self.total_trades > 1 { (self.return_m2 / (n - 1.0)).sqrt() } else { 0.0 };
+            let downside_dev = if self.total_trades > 0 { (self.downside_sq_sum / n).sqrt() } else { 0.0 };
+            let ratio = |denominator: f64| if denominator > 0.0 { self.mean_return / denominator } else { 0.0 };
+            
+            PerformanceMetrics {
+                total_trades: self.total_trades,
+                winning_trades: self.winning_trades,
+                losing_trades: self.losing_trades,
+                win_rate: if n > 0.0 { self.winning_trades as f64 / n } else { 0.0 },
+                total_pnl: self.total_pnl,
+                average_win: if self.winning_trades > 0 { self.gross_profit / self.winning_trades as f64 } else { 0.0 },
+                average_loss: if self.losing_trades > 0 { self.gross_loss / self.losing_trades as f64 } else { 0.0 },
+                profit_factor: if self.gross_loss > 0.0 {
+                    self.gross_profit / self.gross_loss
+                } else if self.gross_profit > 0.0 {
+                    f64::INFINITY
+                } else {
+                    0.0
+                },
+                max_drawdown: self.max_drawdown,
+                sharpe_ratio: ratio(std_dev),
+                total_volume: self.total_volume,
+                last_updated: self.last_updated.unwrap_or_else(Utc::now),
+                sortino_ratio: ratio(downside_dev),
+                best_trade_pnl: self.best_trade_pnl.unwrap_or(0.0),
+                worst_trade_pnl: self.worst_trade_pnl.unwrap_or(0.0),
+                total_fees: self.total_fees,
+                equity_curve: self.equity_curve.clone(),
+            }
+        }
+    }
+    
+    /// Keeps metrics for every slice and day up to date by folding in lots
+    /// closed since the last refresh.
+    #[derive(Debug, Default)]
+    pub struct PerformanceTracker {
+        slices: HashMap<MetricsSlice, Accumulator>,
+        daily: BTreeMap<NaiveDate, DailyStats>,
+        watermark: Option<DateTime<Utc>>,
+        // Lots closed exactly at the watermark, already counted
+        seen_at_watermark: HashSet<String>,
+    }
+    
+    impl PerformanceTracker {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        
+        /// Fold newly closed lots into the metrics. Returns how many were added.
+        pub async fn refresh(&mut self, database: &Database) -> Result<usize, BotError> {
+            let trades = database.get_closed_trades(self.watermark).await?;
+            let mut added = 0;
+            
+            for trade in trades {
+                if Some(trade.closed_at) == self.watermark && self.seen_at_watermark.contains(&trade.lot_id) {
+                    continue;
+                }
+                
+                self.push(&trade);
+                added += 1;
+                
+                if Some(trade.closed_at) != self.watermark {
+                    self.watermark = Some(trade.closed_at);
+                    self.seen_at_watermark.clear();
+                }
+                self.seen_at_watermark.insert(trade.lot_id.clone());
+            }
+            
+            Ok(added)
+        }
+        
+        fn push(&mut self, trade: &ClosedTrade) {
+            self.slices.entry(MetricsSlice::All).or_default().push(trade);
+            self.slices
+                .entry(MetricsSlice::Strategy(trade.strategy.name().to_string()))
+                .or_default()
+                .push(trade);
+            if let Some(trader) = &trade.trader_address {
+                self.slices.entry(MetricsSlice::Trader(trader.clone())).or_default().push(trade);
+            }
+            
+            let date = trade.closed_at.date_naive();
+            let day = self.daily.entry(date).or_insert_with(|| DailyStats {
+                date: date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+                trades_count: 0,
+                pnl: 0.0,
+                volume: 0.0,
+                fees_paid: 0.0,
+                best_trade: None,
+                worst_trade: None,
+            });
+            
+            day.trades_count += 1;
+            day.pnl += trade.realized_pnl;
+            day.volume += trade.cost_sol + trade.proceeds_sol;
+            day.fees_paid += trade.fee_sol;
+            
+            let is_better = |current: &Option<Trade>| current.as_ref().map_or(true, |t| trade.realized_pnl > t.sol_received - t.sol_spent);
+            let is_worse = |current: &Option<Trade>| current.as_ref().map_or(true, |t| trade.realized_pnl < t.sol_received - t.sol_spent);
+            if is_better(&day.best_trade) {
+                day.best_trade = Some(trade.to_trade());
+            }
+            if is_worse(&day.worst_trade) {
+                day.worst_trade = Some(trade.to_trade());
+            }
+        }
+        
+        pub fn metrics(&self, slice: &MetricsSlice) -> Option<PerformanceMetrics> {
+            self.slices.get(slice).map(Accumulator::snapshot)
+        }
+        
+        pub fn slices(&self) -> impl Iterator<Item = &MetricsSlice> {
+            self.slices.keys()
+        }
+        
+        /// Daily stats, oldest first.
+        pub fn daily_stats(&self) -> Vec<DailyStats> {
+            self.daily.values().cloned().collect()
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{
+    config::{BotConfig, DiscoveryConfig},
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::Database,
+    types::{FadedWallet, Trade, Trader},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn, debug};
+use chrono::{DateTime, Duration, Utc};
+
+// Round trips closed within this share of their cost count as break-even
+const WASH_BAND: f64 = 0.005;
+
+/// Finds profitable wallets in recent Heaven swaps and writes the best of
+/// them to the traders table for the copy trader to pick up.
+pub struct TraderDiscovery {
+    config: BotConfig,
+    heaven_client: Arc<HeavenClient>,
+    database: Arc<Database>,
+}
+
+/// Realized performance of one wallet over the scanned swaps.
+#[derive(Debug, Clone)]
+pub struct WalletStats {
+    pub address: String,
+    pub swaps: u64,
+    pub round_trips: u64,
+    pub wins: u64,
+    pub realized_profit: f64,
+    pub volume: f64,
+    /// Largest loss on a single round trip, as a share of its cost
+    pub worst_loss: f64,
+    /// Round trips closed within `WASH_BAND` of break-even
+    pub wash_round_trips: u64,
+    pub hold_secs: Vec<i64>,
+    pub first_trade: DateTime<Utc>,
+    pub last_trade: DateTime<Utc>,
+}
+
+// A wallet's open holding in one token, from its first buy to the sell that empties it
+#[derive(Debug, Default)]
+struct Holding {
+    tokens: f64,
+    cost_sol: f64,
+    opened_at: Option<DateTime<Utc>>,
+    trip_cost: f64,
+    trip_pnl: f64,
+}
+
+impl WalletStats {
+    fn new(address: &str, first_trade: DateTime<Utc>) -> Self {
+        Self {
+            address: address.to_string(),
+            swaps: 0,
+            round_trips: 0,
+            wins: 0,
+            realized_profit: 0.0,
+            volume: 0.0,
+            worst_loss: 0.0,
+            wash_round_trips: 0,
+            hold_secs: Vec::new(),
+            first_trade,
+            last_trade: first_trade,
+        }
+    }
+    
+    pub fn win_rate(&self) -> f64 {
+        if self.round_trips == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.round_trips as f64
+        }
+    }
+    
+    pub fn median_hold_secs(&self) -> i64 {
+        let mut holds = self.hold_secs.clone();
+        holds.sort_unstable();
+        holds.get(holds.len() / 2).copied().unwrap_or(0)
+    }
+    
+    /// Swaps per hour over the wallet's active span, at least an hour
+    pub fn trades_per_hour(&self) -> f64 {
+        let hours = ((self.last_trade - self.first_trade).num_seconds() as f64 / 3600.0).max(1.0);
+        self.swaps as f64 / hours
+    }
+    
+    pub fn wash_share(&self) -> f64 {
+        if self.round_trips == 0 {
+            0.0
+        } else {
+            self.wash_round_trips as f64 / self.round_trips as f64
+        }
+    }
+}
+
+impl TraderDiscovery {
+    pub fn new(
+        config: BotConfig,
+        heaven_client: Arc<HeavenClient>,
+        database: Arc<Database>,
+    ) -> Self {
+        Self {
+            config,
+            heaven_client,
+            database,
+        }
+    }
+    
+    pub async fn run(&self, is_running: Arc<RwLock<bool>>) -> Result<(), BotError> {
+        let mut interval = tokio::time::interval(
+            std::time::Duration::from_secs(self.config.copy_trader.discovery.interval_secs)
+        );
+        
+        while *is_running.read().await {
+            interval.tick().await;
+            
+            if let Err(e) = self.discover().await {
+                warn!("Trader discovery failed: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Scan recent swaps once and record the top wallets. Returns what was written.
+    pub async fn discover(&self) -> Result<Vec<Trader>, BotError> {
+        let settings = &self.config.copy_trader.discovery;
+        
+        let swaps = self.heaven_client.get_recent_swaps(settings.scan_limit, settings.decode_batch_size).await?;
+        let stats = aggregate_wallets(swaps);
+        debug!("Discovery scanned {} wallets", stats.len());
+        
+        let mut candidates: Vec<Trader> = stats.values()
+            .filter(|s| self.is_candidate(s))
+            .map(|s| to_trader(s, risk_score(s, settings)))
+            .filter(|t| t.risk_score <= settings.max_risk_score)
+            .collect();
+        
+        // Profit discounted by risk
+        candidates.sort_by(|a, b| {
+            let score = |t: &Trader| t.total_profit * (1.0 - t.risk_score);
+            score(b).total_cmp(&score(a))
+        });
+        candidates.truncate(settings.top_n);
+        
+        for trader in candidates.iter_mut() {
+            // Keep names and verification set by hand
+            let existing = self.database.get_trader(&trader.address).await?;
+            let is_new = existing.is_none();
+            if let Some(existing) = existing {
+                trader.name = existing.name;
+                trader.is_verified = existing.is_verified;
+            }
+            self.database.record_trader(trader).await?;
+            self.database.record_discovered_trader(&trader.address, is_new).await?;
+        }
+        
+        info!("Discovery recorded {} traders", candidates.len());
+        
+        let cutoff = Utc::now() - Duration::hours(settings.trader_ttl_hours as i64);
+        let expired = self.database.expire_discovered_traders(cutoff).await?;
+        if !expired.is_empty() {
+            info!("Discovery expired {} traders that stopped qualifying", expired.len());
+        }
+        
+        if self.config.copy_trader.fade.enabled {
+            self.record_losers(&stats).await?;
+        }
+        
+        Ok(candidates)
+    }
+    
+    /// Record wallets that keep losing so fade mode can trade against them.
+    async fn record_losers(&self, stats: &HashMap<String, WalletStats>) -> Result<(), BotError> {
+        let fade = &self.config.copy_trader.fade;
+        let min_round_trips = self.config.copy_trader.discovery.min_round_trips;
+        
+        let mut losers: Vec<&WalletStats> = stats.values()
+            .filter(|s| s.round_trips >= min_round_trips)
+            .filter(|s| s.win_rate() <= fade.max_win_rate && s.realized_profit <= -fade.min_loss_sol)
+            .collect();
+        losers.sort_by(|a, b| a.realized_profit.total_cmp(&b.realized_profit));
+        losers.truncate(fade.max_wallets);
+        
+        for loser in &losers {
+            self.database.record_faded_wallet(&FadedWallet {
+                address: loser.address.clone(),
+                realized_pnl: loser.realized_profit,
+                round_trips: loser.round_trips,
+                win_rate: loser.win_rate(),
+                updated_at: Utc::now(),
+            }).await?;
+        }
+        
+        info!("Discovery recorded {} wallets to fade", losers.len());
+        Ok(())
+    }
+    
+    fn is_candidate(&self, stats: &WalletStats) -> bool {
+        let settings = &self.config.copy_trader.discovery;
+        
+        if self.config.copy_trader.blacklisted_traders.contains(&stats.address) {
+            return false;
+        }
+        
+        if stats.round_trips < settings.min_round_trips
+            || stats.win_rate() < settings.min_win_rate
+            || stats.realized_profit < settings.min_realized_profit
+        {
+            return false;
+        }
+        
+        // Bots flip in seconds and trade around the clock
+        if stats.median_hold_secs() < settings.min_median_hold_secs
+            || stats.trades_per_hour() > settings.max_trades_per_hour
+        {
+            debug!("Skipping likely bot {}", stats.address);
+            return false;
+        }
+        
+        // Wash traders cycle the same tokens for volume without taking risk
+        if stats.wash_share() > settings.max_wash_share {
+            debug!("Skipping likely wash trader {}", stats.address);
+            return false;
+        }
+        
+        true
+    }
+}
+
+/// Replay each wallet's swaps per token and tally closed round trips.
+/// Sells of tokens bought before the scanned window are ignored.
+pub fn aggregate_wallets(swaps: Vec<(String, Trade)>) -> HashMap<String, WalletStats> {
+    let mut by_wallet: HashMap<String, Vec<Trade>> = HashMap::new();
+    for (wallet, trade) in swaps {
+        by_wallet.entry(wallet).or_default().push(trade);
+    }
+    
+    let mut stats = HashMap::new();
+    for (wallet, mut trades) in by_wallet {
+        trades.sort_by_key(|t| t.timestamp);
+        let Some(first) = trades.first() else {
+            continue;
+        };
+        
+        let mut wallet_stats = WalletStats::new(&wallet, first.timestamp);
+        let mut holdings: HashMap<String, Holding> = HashMap::new();
+        
+        for trade in &trades {
+            wallet_stats.swaps += 1;
+            wallet_stats.volume += trade.amount_sol;
+            wallet_stats.last_trade = trade.timestamp;
+            
+            let holding = holdings.entry(trade.token_mint.clone()).or_default();
+            if trade.trade_type == "buy" {
+                if holding.opened_at.is_none() {
+                    holding.opened_at = Some(trade.timestamp);
+                }
+                holding.tokens += trade.token_amount;
+                holding.cost_sol += trade.sol_spent;
+                holding.trip_cost += trade.sol_spent;
+                continue;
+            }
+            
+            let Some(opened_at) = holding.opened_at else {
+                continue;
+            };
+            if holding.tokens <= 0.0 {
+                continue;
+            }
+            
+            let fraction = (trade.token_amount / holding.tokens).min(1.0);
+            let cost = holding.cost_sol * fraction;
+            let pnl = trade.sol_received - cost;
+            holding.tokens -= trade.token_amount.min(holding.tokens);
+            holding.cost_sol -= cost;
+            holding.trip_pnl += pnl;
+            wallet_stats.realized_profit += pnl;
+            
+            // Dust left behind still closes the round trip
+            if fraction >= 0.999 {
+                wallet_stats.round_trips += 1;
+                if holding.trip_pnl > 0.0 {
+                    wallet_stats.wins += 1;
+                }
+                if holding.trip_cost > 0.0 {
+                    let trip_return = holding.trip_pnl / holding.trip_cost;
+                    wallet_stats.worst_loss = wallet_stats.worst_loss.max(-trip_return);
+                    if trip_return.abs() <= WASH_BAND {
+                        wallet_stats.wash_round_trips += 1;
+                    }
+                }
+                wallet_stats.hold_secs.push((trade.timestamp - opened_at).num_seconds());
+                *holding = Holding::default();
+            }
+        }
+        
+        stats.insert(wallet, wallet_stats);
+    }
+    
+    stats
+}
+
+/// Risk between 0 (safest) and 1: losing rate, the worst single round trip,
+/// how fast positions are flipped, and how little history backs the numbers.
+pub fn risk_score(stats: &WalletStats, settings: &DiscoveryConfig) -> f64 {
+    let loss_rate = 1.0 - stats.win_rate();
+    let worst_loss = stats.worst_loss.clamp(0.0, 1.0);
+    let median_hold = stats.median_hold_secs().max(1) as f64;
+    let fast_flips = (settings.min_median_hold_secs as f64 * 10.0 / median_hold).min(1.0);
+    let thin_history = (1.0 / (stats.round_trips.max(1) as f64).sqrt()).min(1.0);
+    
+    (0.4 * loss_rate + 0.3 * worst_loss + 0.2 * fast_flips + 0.1 * thin_history).clamp(0.0, 1.0)
+}
+
+fn to_trader(stats: &WalletStats, risk_score: f64) -> Trader {
+    Trader {
+        address: stats.address.clone(),
+        name: format!("discovered-{}", &stats.address[..stats.address.len().min(8)]),
+        total_trades: stats.round_trips,
+        successful_trades: stats.wins,
+        total_profit: stats.realized_profit,
+        win_rate: stats.win_rate(),
+        average_profit: stats.realized_profit / stats.round_trips.max(1) as f64,
+        total_volume: stats.volume,
+        last_trade_time: stats.last_trade,
+        is_verified: false,
+        risk_score,
+    }
+}
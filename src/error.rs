@@ -41,6 +41,9 @@ pub enum BotError {
     #[error("Rate limit exceeded: {0}")]
     RateLimitExceeded(String),
     
+    #[error("Risk limit: {0}")]
+    RiskLimit(String),
+    
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
     
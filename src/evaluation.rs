@@ -0,0 +1,98 @@
+use crate::{
+    config::TraderEvaluationConfig,
+    database::metrics::ClosedTrade,
+    types::TraderStatus,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Our realized results copying one trader over the evaluation window.
+#[derive(Debug, Clone, Default)]
+pub struct CopyScore {
+    pub copies: u64,
+    pub wins: u64,
+    pub pnl_sol: f64,
+    pub cost_sol: f64,
+}
+
+impl CopyScore {
+    pub fn copy_return(&self) -> f64 {
+        if self.cost_sol > 0.0 { self.pnl_sol / self.cost_sol } else { 0.0 }
+    }
+    
+    pub fn win_rate(&self) -> f64 {
+        if self.copies == 0 { 0.0 } else { self.wins as f64 / self.copies as f64 }
+    }
+}
+
+/// Score each trader on their last `window_copies` copies closed after
+/// `since(trader)`. `trades` must be ordered by close time, oldest first.
+pub fn score_copies(
+    trades: &[ClosedTrade],
+    settings: &TraderEvaluationConfig,
+    since: impl Fn(&str) -> Option<DateTime<Utc>>,
+) -> HashMap<String, CopyScore> {
+    let mut by_trader: HashMap<&str, Vec<&ClosedTrade>> = HashMap::new();
+    for trade in trades {
+        let Some(address) = trade.trader_address.as_deref() else {
+            continue;
+        };
+        if since(address).is_some_and(|since| trade.closed_at <= since) {
+            continue;
+        }
+        by_trader.entry(address).or_default().push(trade);
+    }
+    
+    by_trader.into_iter()
+        .map(|(address, copies)| {
+            let recent = &copies[copies.len().saturating_sub(settings.window_copies)..];
+            let mut score = CopyScore::default();
+            for copy in recent {
+                score.copies += 1;
+                score.pnl_sol += copy.realized_pnl;
+                score.cost_sol += copy.cost_sol;
+                if copy.realized_pnl > 0.0 {
+                    score.wins += 1;
+                }
+            }
+            (address.to_string(), score)
+        })
+        .collect()
+}
+
+/// The status a trader moves to given their score, with the reason. Blacklisted
+/// traders are only reinstated by hand.
+pub fn next_status(
+    current: TraderStatus,
+    score: &CopyScore,
+    settings: &TraderEvaluationConfig,
+) -> (TraderStatus, String) {
+    let copy_return = score.copy_return();
+    
+    if score.copies < settings.min_copies as u64 || current == TraderStatus::Blacklisted {
+        return (current, String::new());
+    }
+    
+    if copy_return <= settings.blacklist_return {
+        return (
+            TraderStatus::Blacklisted,
+            format!("copy return {:.1}% at or below {:.1}%", copy_return * 100.0, settings.blacklist_return * 100.0),
+        );
+    }
+    
+    if copy_return <= settings.probation_return {
+        return (
+            TraderStatus::Probation,
+            format!("copy return {:.1}% at or below {:.1}%", copy_return * 100.0, settings.probation_return * 100.0),
+        );
+    }
+    
+    if score.win_rate() < settings.probation_win_rate {
+        return (
+            TraderStatus::Probation,
+            format!("copy win rate {:.1}% below {:.1}%", score.win_rate() * 100.0, settings.probation_win_rate * 100.0),
+        );
+    }
+    
+    (TraderStatus::Active, format!("copy return recovered to {:.1}%", copy_return * 100.0))
+}
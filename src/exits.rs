@@ -0,0 +1,272 @@
+use crate::{
+    config::{ExitRules, TakeProfitLevel, TradingConfig},
+    types::Position,
+};
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+
+// Sells leaving less than this share of the position behind close it outright
+const FULL_EXIT_THRESHOLD: f64 = 0.99;
+
+/// Exit rules for one strategy resolved against the `[trading]` defaults.
+#[derive(Debug, Clone)]
+pub struct ExitPlan {
+    stop_loss: f64,
+    trailing_stop: Option<f64>,
+    ladder: Vec<TakeProfitLevel>,
+    break_even_after_first_tp: bool,
+    max_hold_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitSignal {
+    /// Fraction of the current holding to sell
+    pub fraction: f64,
+    pub reason: ExitReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitReason {
+    StopLoss,
+    BreakEven,
+    TrailingStop,
+    /// Index of the highest ladder level reached
+    TakeProfit(usize),
+    TimeLimit,
+    Script,
+    LeaderExit,
+    KillSwitch,
+    /// A faded wallet bought the token
+    Faded,
+    /// Trimmed back to the allocation targets
+    Rebalance,
+}
+
+impl ExitSignal {
+    pub fn full(reason: ExitReason) -> Self {
+        Self { fraction: 1.0, reason }
+    }
+    
+    pub fn is_full(&self) -> bool {
+        self.fraction >= 1.0
+    }
+}
+
+impl ExitPlan {
+    pub fn new(rules: &ExitRules, trading: &TradingConfig) -> Self {
+        // Without a ladder the flat take-profit sells everything in one step
+        let ladder = if rules.take_profit_ladder.is_empty() {
+            vec![TakeProfitLevel {
+                multiple: 1.0 + rules.take_profit.unwrap_or(trading.profit_taking_percentage),
+                fraction: 1.0,
+            }]
+        } else {
+            rules.take_profit_ladder.clone()
+        };
+        
+        Self {
+            stop_loss: rules.stop_loss.unwrap_or(trading.stop_loss_percentage),
+            trailing_stop: rules.trailing_stop,
+            ladder,
+            break_even_after_first_tp: rules.break_even_after_first_tp,
+            max_hold_secs: rules.max_hold_secs,
+        }
+    }
+    
+    pub fn stop_loss(&self) -> f64 {
+        self.stop_loss
+    }
+    
+    /// The stop price in force and the price of the next untaken take-profit level.
+    pub fn levels(&self, position: &Position) -> (f64, Option<f64>) {
+        let (stop_price, _) = self.stop_price(position);
+        let take_profit = self.ladder
+            .get(position.take_profits_hit as usize)
+            .map(|level| position.entry_price * level.multiple);
+        (stop_price, take_profit)
+    }
+    
+    /// Decide whether `position` should be (partly) sold at its current price.
+    pub fn evaluate(&self, position: &Position) -> Option<ExitSignal> {
+        if position.entry_price <= 0.0 || position.token_amount <= 0.0 {
+            return None;
+        }
+        
+        if let Some(max_hold_secs) = self.max_hold_secs {
+            if (Utc::now() - position.entry_time).num_seconds() >= max_hold_secs as i64 {
+                return Some(ExitSignal::full(ExitReason::TimeLimit));
+            }
+        }
+        
+        if let Some(reason) = self.stop_hit(position) {
+            return Some(ExitSignal::full(reason));
+        }
+        
+        self.take_profit_hit(position)
+    }
+    
+    fn stop_hit(&self, position: &Position) -> Option<ExitReason> {
+        let (stop_price, reason) = self.stop_price(position);
+        (position.current_price <= stop_price).then_some(reason)
+    }
+    
+    // The tightest of the hard, break-even and trailing stops applies
+    fn stop_price(&self, position: &Position) -> (f64, ExitReason) {
+        let mut stop_price = position.entry_price * (1.0 - self.stop_loss);
+        let mut reason = ExitReason::StopLoss;
+        
+        if self.break_even_after_first_tp && position.take_profits_hit > 0 && position.entry_price > stop_price {
+            stop_price = position.entry_price;
+            reason = ExitReason::BreakEven;
+        }
+        
+        if let Some(trailing_stop) = self.trailing_stop {
+            let high_water = position.high_water_price.max(position.entry_price);
+            let trailing_price = high_water * (1.0 - trailing_stop);
+            if trailing_price > stop_price {
+                stop_price = trailing_price;
+                reason = ExitReason::TrailingStop;
+            }
+        }
+        
+        (stop_price, reason)
+    }
+    
+    // Levels skipped over by a fast move are sold together
+    fn take_profit_hit(&self, position: &Position) -> Option<ExitSignal> {
+        let multiple = position.current_price / position.entry_price;
+        let first_level = position.take_profits_hit as usize;
+        
+        let mut fraction_of_initial = 0.0;
+        let mut highest_level = None;
+        for (index, level) in self.ladder.iter().enumerate().skip(first_level) {
+            if multiple < level.multiple {
+                break;
+            }
+            fraction_of_initial += level.fraction;
+            highest_level = Some(index);
+        }
+        
+        let level = highest_level?;
+        let initial = position.initial_token_amount.max(position.token_amount);
+        let mut fraction = (fraction_of_initial * initial / position.token_amount).min(1.0);
+        if fraction >= FULL_EXIT_THRESHOLD {
+            fraction = 1.0;
+        }
+        
+        Some(ExitSignal {
+            fraction,
+            reason: ExitReason::TakeProfit(level),
+        })
+    }
+}
+
+/// Works exit signals off in slices no larger than the pool can absorb,
+/// spaced out in time. Positions without a split exit sell in one go.
+#[derive(Debug, Clone)]
+pub struct ExitSlicer {
+    interval: Duration,
+    pending: HashMap<String, PendingExit>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingExit {
+    tokens_left: f64,
+    reason: ExitReason,
+    next_slice_at: DateTime<Utc>,
+}
+
+/// The part of an exit to sell now.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitSlice {
+    pub tokens: f64,
+    pub reason: ExitReason,
+    /// This slice sells the rest of the position
+    pub closes: bool,
+}
+
+impl ExitSlicer {
+    pub fn new(interval_secs: u64) -> Self {
+        Self {
+            interval: Duration::seconds(interval_secs as i64),
+            pending: HashMap::new(),
+        }
+    }
+    
+    /// Fold a fresh `signal` into the position's exit and return the slice
+    /// due now, if any. `max_tokens` is the largest sell within the impact bound.
+    pub fn next_slice(&mut self, position: &Position, signal: Option<&ExitSignal>, max_tokens: f64) -> Option<ExitSlice> {
+        let now = Utc::now();
+        
+        if let Some(signal) = signal {
+            let wanted = if signal.is_full() { position.token_amount } else { position.token_amount * signal.fraction };
+            match self.pending.get_mut(&position.id) {
+                // Repeats of the signal already being worked off change nothing
+                Some(pending) if pending.reason == signal.reason => {}
+                // A different signal adds to the exit; full exits start immediately
+                Some(pending) => {
+                    pending.tokens_left = if signal.is_full() {
+                        position.token_amount
+                    } else {
+                        (pending.tokens_left + wanted).min(position.token_amount)
+                    };
+                    pending.reason = signal.reason.clone();
+                    if signal.is_full() {
+                        pending.next_slice_at = now;
+                    }
+                }
+                None => {
+                    self.pending.insert(position.id.clone(), PendingExit {
+                        tokens_left: wanted,
+                        reason: signal.reason.clone(),
+                        next_slice_at: now,
+                    });
+                }
+            }
+        }
+        
+        let pending = self.pending.get(&position.id)?;
+        if now < pending.next_slice_at {
+            return None;
+        }
+        
+        let tokens = pending.tokens_left.min(position.token_amount).min(max_tokens.max(0.0));
+        if tokens <= 0.0 {
+            return None;
+        }
+        
+        let closes = position.token_amount > 0.0 && tokens / position.token_amount >= FULL_EXIT_THRESHOLD;
+        Some(ExitSlice {
+            tokens: if closes { position.token_amount } else { tokens },
+            reason: pending.reason.clone(),
+            closes,
+        })
+    }
+    
+    /// Account for a sold slice and schedule the next one.
+    pub fn record_sold(&mut self, position_id: &str, tokens: f64) {
+        let Some(pending) = self.pending.get_mut(position_id) else {
+            return;
+        };
+        
+        pending.tokens_left -= tokens;
+        if pending.tokens_left <= 0.0 {
+            self.pending.remove(position_id);
+        } else {
+            pending.next_slice_at = Utc::now() + self.interval;
+        }
+    }
+    
+    pub fn forget(&mut self, position_id: &str) {
+        self.pending.remove(position_id);
+    }
+    
+    pub fn is_pending(&self, position_id: &str) -> bool {
+        self.pending.contains_key(position_id)
+    }
+    
+    /// Ids of exits with slices still to sell.
+    pub fn pending_ids(&self) -> Vec<String> {
+        self.pending.keys().cloned().collect()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    config::{CopyTraderConfig, FadeConfig},
+    error::BotError,
+    database::Database,
+    heaven_client::HeavenClient,
+    types::{TokenLaunch, Trade},
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use chrono::{DateTime, Duration, Utc};
+
+/// Negative-signal feed: tokens bought by consistently losing wallets are put
+/// on a cool-down that the copy trader and sniper both respect. The feed runs
+/// on its own, so the sniper gets cool-downs without the copy trader.
+pub struct FadeFeed {
+    config: FadeConfig,
+    heaven_client: Arc<HeavenClient>,
+    database: Arc<Database>,
+    // Wallets from the config, kept when discovered wallets expire
+    configured: HashSet<String>,
+    wallets: RwLock<HashSet<String>>,
+    // Token mint to the end of its cool-down
+    cooldowns: RwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+impl FadeFeed {
+    pub fn new(config: &CopyTraderConfig, heaven_client: Arc<HeavenClient>, database: Arc<Database>) -> Self {
+        let mut configured: HashSet<String> = config.fade.wallets.iter().cloned().collect();
+        if config.fade.fade_blacklisted {
+            configured.extend(config.blacklisted_traders.iter().cloned());
+        }
+        
+        Self {
+            config: config.fade.clone(),
+            heaven_client,
+            database,
+            wallets: RwLock::new(configured.clone()),
+            configured,
+            cooldowns: RwLock::new(HashMap::new()),
+        }
+    }
+    
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+    
+    /// Reload the wallets, poll their trades and drop expired cool-downs
+    /// until the bot stops.
+    pub async fn run(&self, is_running: Arc<RwLock<bool>>) -> Result<(), BotError> {
+        let mut interval = tokio::time::interval(
+            std::time::Duration::from_secs(self.config.interval_secs)
+        );
+        
+        while *is_running.read().await {
+            interval.tick().await;
+            
+            if let Err(e) = self.load().await {
+                warn!("Failed to reload faded wallets: {}", e);
+            }
+            self.scan().await;
+            self.prune().await;
+        }
+        
+        Ok(())
+    }
+    
+    /// Fade the configured wallets and the losing wallets discovery recorded
+    /// within `wallet_ttl_hours`. Older records are deleted.
+    pub async fn load(&self) -> Result<(), BotError> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        
+        let cutoff = Utc::now() - Duration::hours(self.config.wallet_ttl_hours as i64);
+        let expired = self.database.expire_faded_wallets(cutoff).await?;
+        if expired > 0 {
+            info!("Stopped fading {} wallets that were not seen losing again", expired);
+        }
+        
+        let faded = self.database.get_faded_wallets(self.config.max_wallets).await?;
+        let mut wallets = self.configured.clone();
+        wallets.extend(faded.into_iter().map(|w| w.address));
+        
+        let mut current = self.wallets.write().await;
+        if *current != wallets {
+            info!("Fading {} wallets", wallets.len());
+            *current = wallets;
+        }
+        
+        Ok(())
+    }
+    
+    /// Poll the faded wallets' trades. Cool-downs run from each buy, so buys
+    /// seen again or from before the feed started are safe to record.
+    async fn scan(&self) {
+        let wallets = self.wallets().await;
+        futures::future::join_all(wallets.iter().map(|address| async move {
+            match self.heaven_client.get_trader_trades(address).await {
+                Ok(trades) => self.record_trades(address, &trades).await,
+                Err(e) => warn!("Failed to scan faded wallet {}: {}", address, e),
+            }
+        })).await;
+    }
+    
+    /// Buys by faded wallets put the token on cool-down.
+    pub async fn record_trades(&self, wallet: &str, trades: &[Trade]) {
+        for trade in trades.iter().filter(|t| t.trade_type == "buy") {
+            self.record_buy(wallet, &trade.token_mint, trade.timestamp).await;
+        }
+    }
+    
+    pub async fn wallets(&self) -> Vec<String> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.wallets.read().await.iter().cloned().collect()
+    }
+    
+    pub async fn is_faded(&self, wallet: &str) -> bool {
+        self.config.enabled && self.wallets.read().await.contains(wallet)
+    }
+    
+    /// A faded wallet bought `token_mint`; avoid it for the cool-down.
+    pub async fn record_buy(&self, wallet: &str, token_mint: &str, at: DateTime<Utc>) {
+        let until = at + Duration::seconds(self.config.cooldown_secs as i64);
+        if until <= Utc::now() {
+            return;
+        }
+        
+        let mut cooldowns = self.cooldowns.write().await;
+        if cooldowns.get(token_mint).is_some_and(|current| *current >= until) {
+            return;
+        }
+        cooldowns.insert(token_mint.to_string(), until);
+        info!("Faded wallet {} bought {}, avoiding it until {}", wallet, token_mint, until);
+    }
+    
+    pub async fn in_cooldown(&self, token_mint: &str) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        self.cooldowns.read().await
+            .get(token_mint)
+            .is_some_and(|until| *until > Utc::now())
+    }
+    
+    /// Whether our inventory of `token_mint` should be sold.
+    pub async fn sell_due(&self, token_mint: &str) -> bool {
+        self.config.sell_inventory && self.in_cooldown(token_mint).await
+    }
+    
+    /// Launches on cool-down or created by a faded wallet.
+    pub async fn avoids_launch(&self, launch: &TokenLaunch) -> bool {
+        if self.in_cooldown(&launch.token_mint).await {
+            return true;
+        }
+        match &launch.creator_address {
+            Some(creator) => self.is_faded(creator).await,
+            None => false,
+        }
+    }
+    
+    pub async fn prune(&self) {
+        let now = Utc::now();
+        self.cooldowns.write().await.retain(|_, until| *until > now);
+    }
+}
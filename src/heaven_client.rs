@@ -3,21 +3,35 @@ use crate::{
     error::BotError,
     types::{
         TokenLaunch, TokenInfo, TradeQuote, PoolState, ProtocolConfig,
-        FeeStructure, FeeType, FlywheelInfo, BuybackEvent,
+        FeeStructure, FeeType, FlywheelInfo, BuybackEvent, Fill, Trade, CopyTrade,
+        SniperStrategy,
     },
 };
-use solana_client::rpc_client::RpcClient;
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+};
 use solana_sdk::{
-    signature::Keypair,
+    signature::{Keypair, Signature},
     pubkey::Pubkey,
     instruction::Instruction,
     compute_budget::ComputeBudgetInstruction,
+    commitment_config::CommitmentConfig,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiTransactionEncoding, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
 };
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{info, warn, error, debug};
 use serde_json::Value;
 use reqwest::Client;
 
+// A just-confirmed transaction can take a moment to show up in getTransaction
+const FILL_READ_ATTEMPTS: u32 = 3;
+const FILL_RETRY_DELAY_MS: u64 = 500;
+
 pub struct HeavenClient {
     config: HeavenConfig,
     rpc_client: Arc<RpcClient>,
@@ -63,13 +77,28 @@ impl HeavenClient {
         Ok(balance as f64 / 1e9) // Convert lamports to SOL
     }
     
+    /// SOL balance of any wallet, such as a copied trader's.
+    pub async fn get_wallet_sol_balance(&self, owner: &str) -> Result<f64, BotError> {
+        let balance = self.rpc_client.get_balance(&parse_pubkey(owner)?)?;
+        Ok(balance as f64 / 1e9)
+    }
+    
     // Token operations
     pub async fn get_token_balance(&self, token_mint: &str) -> Result<f64, BotError> {
+        self.token_balance_of(&self.wallet.pubkey(), token_mint).await
+    }
+    
+    /// Balance of `token_mint` held in any wallet's associated token account.
+    pub async fn get_wallet_token_balance(&self, owner: &str, token_mint: &str) -> Result<f64, BotError> {
+        self.token_balance_of(&parse_pubkey(owner)?, token_mint).await
+    }
+    
+    async fn token_balance_of(&self, owner: &Pubkey, token_mint: &str) -> Result<f64, BotError> {
         let mint_pubkey = Pubkey::from_str(token_mint)
             .map_err(|e| BotError::Validation(format!("Invalid token mint: {}", e)))?;
         
         let ata = spl_associated_token_account::get_associated_token_address(
-            &self.wallet.pubkey(),
+            owner,
             &mint_pubkey,
         );
         
@@ -79,7 +108,9 @@ impl HeavenClient {
                     .map_err(|e| BotError::Token(format!("Failed to parse token balance: {}", e)))?;
                 Ok(amount as f64 / 10f64.powi(balance.decimals as i32))
             }
-            Err(_) => Ok(0.0), // Token account doesn't exist
+            // Only a missing token account means nothing is held; other failures are not a zero balance
+            Err(e) if e.to_string().contains("could not find account") => Ok(0.0),
+            Err(e) => Err(e.into()),
         }
     }
     
@@ -138,6 +169,7 @@ impl HeavenClient {
             slippage,
             fee: fee_amount as f64 / 1e9,
             fee_pct: total_fee_rate,
+            price_impact: buy_price_impact(sol_reserve, sol_after_fees as f64),
         })
     }
     
@@ -182,9 +214,31 @@ impl HeavenClient {
             slippage,
             fee: fee_amount as f64 / 1e9,
             fee_pct: total_fee_rate,
+            price_impact: sell_price_impact(token_reserve, token_base_units as f64),
         })
     }
     
+    /// Largest buy, in SOL, whose price impact stays within `max_impact`.
+    pub async fn max_buy_for_impact(&self, token_mint: &str, max_impact: f64) -> Result<f64, BotError> {
+        let pool_state = self.get_pool_state(token_mint).await?;
+        let total_fee_rate = pool_state.fee_rate + pool_state.protocol_fee_rate + pool_state.creator_fee_rate;
+        
+        // Impact is driven by what reaches the pool after fees
+        let sol_into_pool = pool_state.token_b.liquidity_sol * max_impact.max(0.0);
+        Ok(sol_into_pool / (1.0 - total_fee_rate).max(f64::EPSILON))
+    }
+    
+    /// Largest sell, in tokens, whose price impact stays within `max_impact`.
+    pub async fn max_sell_for_impact(&self, token_mint: &str, max_impact: f64) -> Result<f64, BotError> {
+        let pool_state = self.get_pool_state(token_mint).await?;
+        if max_impact >= 1.0 {
+            return Ok(f64::MAX);
+        }
+        
+        let token_reserve = pool_state.token_a.liquidity_sol;
+        Ok(token_reserve * max_impact.max(0.0) / (1.0 - max_impact))
+    }
+    
     pub async fn create_buy_instruction(
         &self,
         token_mint: &str,
@@ -213,7 +267,14 @@ impl HeavenClient {
     pub async fn get_pool_state(&self, token_mint: &str) -> Result<PoolState, BotError> {
         // Get pool account data
         let pool_key = self.derive_pool_key(token_mint).await?;
-        let pool_account = self.rpc_client.get_account(&pool_key)?;
+        let pool_account = match self.rpc_client.get_account(&pool_key) {
+            Ok(account) => account,
+            // A closed pool is reported as such so callers can tell it from an RPC failure
+            Err(e) if e.to_string().contains("could not find account") => {
+                return Err(BotError::PoolNotFound(token_mint.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
         
         // Parse pool state (this would use Heaven's SDK)
         // For now, returning mock data
@@ -332,6 +393,296 @@ impl HeavenClient {
         Ok(vec![])
     }
     
+    /// Read what a confirmed swap actually did to our wallet's SOL and
+    /// `token_mint` balances. The wallet is the fee payer, so it is account 0.
+    pub async fn get_fill(&self, signature: &str, token_mint: &str) -> Result<Fill, BotError> {
+        let parsed_signature = Signature::from_str(signature)
+            .map_err(|e| BotError::Validation(format!("Invalid signature: {}", e)))?;
+        
+        let transaction = self.rpc_client.get_transaction_with_config(
+            &parsed_signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        
+        let meta = transaction.transaction.meta
+            .ok_or_else(|| BotError::Transaction(format!("No status meta for {}", signature)))?;
+        if let Some(err) = meta.err {
+            return Err(BotError::Transaction(format!("Transaction {} failed: {:?}", signature, err)));
+        }
+        
+        let pre_lamports = *meta.pre_balances.first()
+            .ok_or_else(|| BotError::Transaction(format!("No balances for {}", signature)))?;
+        let post_lamports = *meta.post_balances.first()
+            .ok_or_else(|| BotError::Transaction(format!("No balances for {}", signature)))?;
+        
+        let owner = self.wallet.pubkey().to_string();
+        let pre_tokens = Self::owned_token_balance(&meta.pre_token_balances, &owner, token_mint);
+        let post_tokens = Self::owned_token_balance(&meta.post_token_balances, &owner, token_mint);
+        let rent_lamports = Self::token_account_rent(&meta, &owner, token_mint);
+        
+        Ok(Fill {
+            signature: signature.to_string(),
+            slot: transaction.slot,
+            token_delta: post_tokens - pre_tokens,
+            sol_delta: (post_lamports as f64 - pre_lamports as f64 + rent_lamports) / 1e9,
+            fee_sol: meta.fee as f64 / 1e9,
+        })
+    }
+    
+    /// Read a fill, retrying while the transaction may not be indexed yet.
+    async fn read_fill(&self, signature: &str, token_mint: &str) -> Result<Fill, BotError> {
+        let mut attempt = 1;
+        loop {
+            match self.get_fill(signature, token_mint).await {
+                Ok(fill) => return Ok(fill),
+                Err(e) if attempt >= FILL_READ_ATTEMPTS => return Err(e),
+                Err(e) => {
+                    debug!("Fill for {} not readable yet (attempt {}): {}", signature, attempt, e);
+                    tokio::time::sleep(std::time::Duration::from_millis(FILL_RETRY_DELAY_MS)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+    
+    /// Update an executed trade with its real fill. If the transaction can't
+    /// be read back the trade is marked unsettled and the error returned, so
+    /// no PnL is booked against amounts we don't know.
+    pub async fn settle_trade(&self, trade: &mut Trade, signature: &str) -> Result<(), BotError> {
+        match self.read_fill(signature, &trade.token_mint).await {
+            Ok(fill) => {
+                trade.apply_fill(&fill);
+                Ok(())
+            }
+            Err(e) => {
+                trade.mark_unsettled(signature);
+                Err(e)
+            }
+        }
+    }
+    
+    pub async fn settle_copy_trade(&self, copy_trade: &mut CopyTrade, signature: &str) -> Result<(), BotError> {
+        match self.read_fill(signature, &copy_trade.token_mint).await {
+            Ok(fill) => {
+                copy_trade.apply_fill(&fill);
+                Ok(())
+            }
+            Err(e) => {
+                copy_trade.mark_unsettled(signature);
+                Err(e)
+            }
+        }
+    }
+    
+    /// Decode a tracked wallet's Heaven swap from its confirmed transaction.
+    /// Returns `None` for failed transactions, transactions the wallet did not
+    /// pay for, and anything that is not a swap against SOL. This blocks on
+    /// RPC, so async callers should run it with `spawn_blocking`.
+    pub fn decode_leader_swap(&self, signature: &str, trader_address: &str) -> Result<Option<Trade>, BotError> {
+        Ok(self.decode_swap(signature)?
+            .filter(|(wallet, _)| wallet == trader_address)
+            .map(|(_, trade)| trade))
+    }
+    
+    /// Decode any wallet's swap, returning the fee payer with the trade. Blocks on RPC.
+    pub fn decode_swap(&self, signature: &str) -> Result<Option<(String, Trade)>, BotError> {
+        let parsed_signature = Signature::from_str(signature)
+            .map_err(|e| BotError::Validation(format!("Invalid signature {}: {}", signature, e)))?;
+        let transaction = self.rpc_client.get_transaction_with_config(
+            &parsed_signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        
+        let Some(meta) = transaction.transaction.meta else {
+            return Ok(None);
+        };
+        if meta.err.is_some() {
+            return Ok(None);
+        }
+        
+        // Wallets sign their own swaps, so the trader is the fee payer
+        let Some(fee_payer) = transaction.transaction.transaction.decode()
+            .and_then(|tx| tx.message.static_account_keys().first().copied())
+        else {
+            return Ok(None);
+        };
+        let trader_address = fee_payer.to_string();
+        let trader_address = trader_address.as_str();
+        
+        // The traded token is the one whose balance moved the most
+        let native_mint = spl_token::native_mint::ID.to_string();
+        let mut mints: Vec<&str> = Vec::new();
+        for balances in [&meta.pre_token_balances, &meta.post_token_balances] {
+            if let OptionSerializer::Some(balances) = balances {
+                for balance in balances {
+                    if balance.mint != native_mint && !mints.contains(&balance.mint.as_str()) {
+                        mints.push(&balance.mint);
+                    }
+                }
+            }
+        }
+        let Some((token_mint, token_delta, token_balance_before)) = mints.into_iter()
+            .map(|mint| {
+                let pre = Self::owned_token_balance(&meta.pre_token_balances, trader_address, mint);
+                let post = Self::owned_token_balance(&meta.post_token_balances, trader_address, mint);
+                (mint.to_string(), post - pre, pre)
+            })
+            .filter(|(_, delta, _)| *delta != 0.0)
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+        else {
+            return Ok(None);
+        };
+        
+        let (Some(pre_lamports), Some(post_lamports)) = (meta.pre_balances.first(), meta.post_balances.first()) else {
+            return Ok(None);
+        };
+        // Rent for opening or closing the leader's token account is not part of the price
+        let rent_lamports = Self::token_account_rent(&meta, trader_address, &token_mint);
+        let fill = Fill {
+            signature: signature.to_string(),
+            slot: transaction.slot,
+            token_delta,
+            sol_delta: (*post_lamports as f64 - *pre_lamports as f64 + rent_lamports) / 1e9,
+            fee_sol: meta.fee as f64 / 1e9,
+        };
+        
+        let timestamp = transaction.block_time
+            .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+            .unwrap_or_else(chrono::Utc::now);
+        
+        let mut trade = Trade {
+            id: signature.to_string(),
+            token_mint,
+            trade_type: if token_delta > 0.0 { "buy" } else { "sell" }.to_string(),
+            amount_sol: 0.0,
+            token_amount: 0.0,
+            price: 0.0,
+            slippage: 0.0,
+            strategy: SniperStrategy::Custom("Leader".to_string()),
+            timestamp,
+            status: "pending".to_string(),
+            transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: Some(token_balance_before),
+        };
+        trade.apply_fill(&fill);
+        
+        Ok(Some((trader_address.to_string(), trade)))
+    }
+    
+    /// Decode the most recent swaps against the Heaven program, newest first,
+    /// paging back through up to `limit` signatures. Transactions are read on
+    /// the blocking pool, `batch_size` at a time.
+    pub async fn get_recent_swaps(self: &Arc<Self>, limit: usize, batch_size: usize) -> Result<Vec<(String, Trade)>, BotError> {
+        let program_id = parse_pubkey(&self.config.program_id)?;
+        let mut swaps = Vec::new();
+        let mut before = None;
+        let mut scanned = 0;
+        
+        while scanned < limit {
+            let client = self.clone();
+            let page_limit = (limit - scanned).min(1000);
+            let page = tokio::task::spawn_blocking(move || {
+                client.rpc_client.get_signatures_for_address_with_config(
+                    &program_id,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until: None,
+                        limit: Some(page_limit),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                    },
+                )
+            }).await
+                .map_err(|e| BotError::Network(format!("Signature scan task failed: {}", e)))??;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(Signature::from_str(&last.signature)
+                .map_err(|e| BotError::Validation(format!("Invalid signature {}: {}", last.signature, e)))?);
+            scanned += page.len();
+            
+            let signatures: Vec<String> = page.into_iter()
+                .filter(|s| s.err.is_none())
+                .map(|s| s.signature)
+                .collect();
+            for batch in signatures.chunks(batch_size.max(1)) {
+                let decoded = futures::future::join_all(batch.iter().map(|signature| {
+                    let client = self.clone();
+                    let signature = signature.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let decoded = client.decode_swap(&signature);
+                        (signature, decoded)
+                    })
+                })).await;
+                
+                for result in decoded {
+                    match result {
+                        Ok((_, Ok(Some(swap)))) => swaps.push(swap),
+                        Ok((_, Ok(None))) => {}
+                        Ok((signature, Err(e))) => debug!("Skipping undecodable transaction {}: {}", signature, e),
+                        Err(e) => warn!("Swap decode task failed: {}", e),
+                    }
+                }
+            }
+        }
+        
+        Ok(swaps)
+    }
+    
+    /// Lamports that moved into (positive) or out of `owner`'s token accounts
+    /// for `token_mint`. Token accounts only hold rent, so this is the rent paid
+    /// to open the account on a buy, or refunded by closing it on a sell.
+    fn token_account_rent(meta: &UiTransactionStatusMeta, owner: &str, token_mint: &str) -> f64 {
+        let mut accounts: Vec<usize> = Vec::new();
+        for balances in [&meta.pre_token_balances, &meta.post_token_balances] {
+            if let OptionSerializer::Some(balances) = balances {
+                for balance in balances {
+                    let owned = matches!(&balance.owner, OptionSerializer::Some(o) if o == owner);
+                    let index = balance.account_index as usize;
+                    if owned && balance.mint == token_mint && !accounts.contains(&index) {
+                        accounts.push(index);
+                    }
+                }
+            }
+        }
+        
+        accounts.into_iter()
+            .map(|index| {
+                let pre = meta.pre_balances.get(index).copied().unwrap_or(0) as f64;
+                let post = meta.post_balances.get(index).copied().unwrap_or(0) as f64;
+                post - pre
+            })
+            .sum()
+    }
+    
+    // A token account missing from the meta had a zero balance
+    fn owned_token_balance(
+        balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+        owner: &str,
+        token_mint: &str,
+    ) -> f64 {
+        let OptionSerializer::Some(balances) = balances else {
+            return 0.0;
+        };
+        
+        balances.iter()
+            .filter(|b| b.mint == token_mint)
+            .filter(|b| matches!(&b.owner, OptionSerializer::Some(o) if o == owner))
+            .filter_map(|b| b.ui_token_amount.ui_amount_string.parse::<f64>().ok())
+            .sum()
+    }
+    
     // Trader operations
     pub async fn get_trader_trades(&self, trader_address: &str) -> Result<Vec<crate::types::Trade>, BotError> {
         // Get recent trades from a specific trader
@@ -385,18 +736,17 @@ impl HeavenClient {
     
     // Market data
     pub async fn get_market_data(&self, token_mint: &str) -> Result<crate::types::MarketData, BotError> {
-        // Get comprehensive market data for a token
-        // This could include price, volume, market cap, etc.
-        // For now, returning mock data
+        // Get comprehensive market data for a token; fails with PoolNotFound once the pool is closed
+        let token = self.get_pool_state(token_mint).await?.token_a;
         Ok(crate::types::MarketData {
             token_mint: token_mint.to_string(),
-            price: 0.001,
-            volume_24h: 100.0,
-            market_cap: 1000.0,
+            price: token.price,
+            volume_24h: token.volume_24h,
+            market_cap: token.market_cap,
             price_change_1h: 0.0,
-            price_change_24h: 0.0,
+            price_change_24h: token.price_change_24h,
             price_change_7d: 0.0,
-            liquidity: 1.0,
+            liquidity: token.liquidity_sol,
             holders: 100,
             last_updated: chrono::Utc::now(),
         })
@@ -422,3 +772,19 @@ fn parse_pubkey(s: &str) -> Result<Pubkey, BotError> {
 fn pubkey_to_string(pubkey: &Pubkey) -> String {
     pubkey.to_string()
 }
+
+// A constant-product buy of `dx` pays on average `dx / x` above spot
+fn buy_price_impact(sol_reserve: f64, sol_in: f64) -> f64 {
+    if sol_reserve <= 0.0 {
+        return 1.0;
+    }
+    sol_in / sol_reserve
+}
+
+// A constant-product sell of `dy` receives on average `dy / (y + dy)` below spot
+fn sell_price_impact(token_reserve: f64, tokens_in: f64) -> f64 {
+    if token_reserve + tokens_in <= 0.0 {
+        return 1.0;
+    }
+    tokens_in / (token_reserve + tokens_in)
+}
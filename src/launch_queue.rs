@@ -0,0 +1,258 @@
+use crate::{
+    config::LaunchQueueConfig,
+    types::{SnipePriority, SniperStrategy, TokenLaunch},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Mutex as StdMutex;
+use tokio::sync::Notify;
+
+/// Matched launches waiting for a snipe worker, highest priority first.
+pub struct LaunchQueue {
+    config: LaunchQueueConfig,
+    launches: StdMutex<BinaryHeap<QueuedLaunch>>,
+    available: Notify,
+    next_seq: AtomicU64,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedLaunch {
+    pub launch: TokenLaunch,
+    pub strategy: SniperStrategy,
+    pub priority: SnipePriority,
+    /// Number of enabled strategies the launch matched
+    pub strength: usize,
+    pub queued_at: DateTime<Utc>,
+    seq: u64,
+}
+
+impl QueuedLaunch {
+    pub fn is_stale(&self, now: DateTime<Utc>, max_age_ms: u64) -> bool {
+        now - self.launch.launch_time > Duration::milliseconds(max_age_ms as i64)
+    }
+}
+
+// Priority, then match strength, then first queued
+impl Ord for QueuedLaunch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+            .then(self.strength.cmp(&other.strength))
+            .then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for QueuedLaunch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedLaunch {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl Eq for QueuedLaunch {}
+
+impl LaunchQueue {
+    pub fn new(config: LaunchQueueConfig) -> Self {
+        Self {
+            config,
+            launches: StdMutex::new(BinaryHeap::new()),
+            available: Notify::new(),
+            next_seq: AtomicU64::new(0),
+        }
+    }
+    
+    /// Queue a launch. Returns the launch dropped to stay within capacity, if any,
+    /// which is the new launch itself when everything queued outranks it.
+    pub fn push(
+        &self,
+        launch: TokenLaunch,
+        strategy: SniperStrategy,
+        priority: SnipePriority,
+        strength: usize,
+    ) -> Option<QueuedLaunch> {
+        let queued = QueuedLaunch {
+            launch,
+            strategy,
+            priority,
+            strength,
+            queued_at: Utc::now(),
+            seq: self.next_seq.fetch_add(1, AtomicOrdering::Relaxed),
+        };
+        
+        let mut launches = self.lock();
+        launches.push(queued);
+        
+        let evicted = if launches.len() > self.config.capacity {
+            let mut queued: Vec<QueuedLaunch> = std::mem::take(&mut *launches).into_vec();
+            let lowest = queued.iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.cmp(b))
+                .map(|(index, _)| index);
+            let evicted = lowest.map(|index| queued.swap_remove(index));
+            *launches = BinaryHeap::from(queued);
+            evicted
+        } else {
+            None
+        };
+        drop(launches);
+        
+        self.available.notify_one();
+        evicted
+    }
+    
+    pub fn pop(&self) -> Option<QueuedLaunch> {
+        self.lock().pop()
+    }
+    
+    /// Wait for the highest priority launch.
+    pub async fn next(&self) -> QueuedLaunch {
+        loop {
+            if let Some(queued) = self.pop() {
+                return queued;
+            }
+            self.available.notified().await;
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    // The heap is left whole between operations, so a poisoned lock is still usable
+    fn lock(&self) -> std::sync::MutexGuard<'_, BinaryHeap<QueuedLaunch>> {
+        self.launches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn queue(capacity: usize) -> LaunchQueue {
+        LaunchQueue::new(LaunchQueueConfig {
+            capacity,
+            ..LaunchQueueConfig::default()
+        })
+    }
+    
+    fn launch(token_mint: &str, launch_time: DateTime<Utc>) -> TokenLaunch {
+        TokenLaunch {
+            token_mint: token_mint.to_string(),
+            token_name: String::new(),
+            token_symbol: String::new(),
+            launch_time,
+            initial_price: 0.001,
+            price: 0.001,
+            market_cap: 1000.0,
+            liquidity_sol: 1.0,
+            volume_24h: 0.0,
+            token_type: "creator".to_string(),
+            has_flywheel: false,
+            flywheel_activity: 0.0,
+            creator_address: None,
+            social_links: Vec::new(),
+            description: String::new(),
+        }
+    }
+    
+    fn push(queue: &LaunchQueue, token_mint: &str, priority: SnipePriority, strength: usize) -> Option<QueuedLaunch> {
+        queue.push(launch(token_mint, Utc::now()), SniperStrategy::CreatorToken, priority, strength)
+    }
+    
+    fn drain(queue: &LaunchQueue) -> Vec<String> {
+        std::iter::from_fn(|| queue.pop()).map(|q| q.launch.token_mint).collect()
+    }
+    
+    #[test]
+    fn pops_by_priority_then_strength_then_arrival() {
+        let queue = queue(10);
+        push(&queue, "low", SnipePriority::Low, 3);
+        push(&queue, "medium_first", SnipePriority::Medium, 1);
+        push(&queue, "high", SnipePriority::High, 1);
+        push(&queue, "medium_strong", SnipePriority::Medium, 2);
+        push(&queue, "medium_second", SnipePriority::Medium, 1);
+        
+        assert_eq!(drain(&queue), ["high", "medium_strong", "medium_first", "medium_second", "low"]);
+    }
+    
+    #[test]
+    fn full_queue_evicts_the_lowest_ranked_launch() {
+        let queue = queue(2);
+        push(&queue, "low", SnipePriority::Low, 1);
+        push(&queue, "medium", SnipePriority::Medium, 1);
+        
+        let evicted = push(&queue, "high", SnipePriority::High, 1);
+        assert_eq!(evicted.map(|q| q.launch.token_mint).as_deref(), Some("low"));
+        assert_eq!(drain(&queue), ["high", "medium"]);
+    }
+    
+    #[test]
+    fn full_queue_evicts_a_new_launch_everything_outranks() {
+        let queue = queue(1);
+        push(&queue, "high", SnipePriority::High, 1);
+        
+        let evicted = push(&queue, "low", SnipePriority::Low, 1);
+        assert_eq!(evicted.map(|q| q.launch.token_mint).as_deref(), Some("low"));
+        assert_eq!(queue.len(), 1);
+    }
+    
+    #[test]
+    fn equal_launches_evict_the_latest_arrival() {
+        let queue = queue(1);
+        push(&queue, "first", SnipePriority::Medium, 1);
+        
+        let evicted = push(&queue, "second", SnipePriority::Medium, 1);
+        assert_eq!(evicted.map(|q| q.launch.token_mint).as_deref(), Some("second"));
+        assert_eq!(drain(&queue), ["first"]);
+    }
+    
+    #[test]
+    fn launches_past_the_max_age_are_stale() {
+        let queue = queue(10);
+        let now = Utc::now();
+        queue.push(launch("old", now - Duration::milliseconds(6000)), SniperStrategy::CreatorToken, SnipePriority::High, 1);
+        queue.push(launch("fresh", now - Duration::milliseconds(1000)), SniperStrategy::CreatorToken, SnipePriority::Low, 1);
+        
+        let old = queue.pop().unwrap();
+        let fresh = queue.pop().unwrap();
+        assert!(old.is_stale(now, 5000));
+        assert!(!fresh.is_stale(now, 5000));
+    }
+    
+    #[test]
+    fn poisoned_lock_keeps_the_queue_usable() {
+        let queue = queue(10);
+        push(&queue, "queued", SnipePriority::Medium, 1);
+        
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = queue.launches.lock().unwrap();
+            panic!("poison the lock");
+        }));
+        
+        assert!(queue.launches.is_poisoned());
+        assert_eq!(drain(&queue), ["queued"]);
+    }
+    
+    #[tokio::test]
+    async fn next_waits_for_a_push() {
+        let queue = std::sync::Arc::new(queue(10));
+        let waiting = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.next().await.launch.token_mint }
+        });
+        
+        tokio::task::yield_now().await;
+        push(&queue, "pushed", SnipePriority::Medium, 1);
+        assert_eq!(waiting.await.unwrap(), "pushed");
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    config::CostBasisMethod,
+    error::BotError,
+    database::{Database, metrics::{MetricsSlice, PerformanceTracker}},
+    types::{DailyStats, Lot, LotClosure, PerformanceMetrics, PnlBreakdown, PnlGroup, Position},
+};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, warn};
+use chrono::Utc;
+
+// Lots with less than this left are fully consumed
+const DUST_TOKENS: f64 = 1e-9;
+
+/// Cost-basis ledger. Every buy opens a lot; sells are matched against the
+/// open lots of the same book (token, strategy and copied trader) and the
+/// realized PnL of each match is stored as a lot closure.
+pub struct Ledger {
+    database: Arc<Database>,
+    method: CostBasisMethod,
+    // Serializes read-modify-write of lots
+    write_lock: Mutex<()>,
+    performance: RwLock<PerformanceTracker>,
+}
+
+impl Ledger {
+    pub fn new(database: Arc<Database>, method: CostBasisMethod) -> Self {
+        Self {
+            database,
+            method,
+            write_lock: Mutex::new(()),
+            performance: RwLock::new(PerformanceTracker::new()),
+        }
+    }
+    
+    /// Open a lot for a new position. `position.amount_sol` already includes `fee_sol`.
+    pub async fn record_buy(&self, position: &Position, fee_sol: f64) -> Result<(), BotError> {
+        let lot = Lot {
+            id: position.id.clone(),
+            token_mint: position.token_mint.clone(),
+            source: position.source.clone(),
+            strategy: position.strategy.clone(),
+            trader_address: position.trader_address.clone(),
+            token_amount: position.token_amount,
+            remaining_tokens: position.token_amount,
+            cost_sol: position.amount_sol,
+            remaining_cost_sol: position.amount_sol,
+            fee_sol,
+            entry_time: position.entry_time,
+            status: "open".to_string(),
+        };
+        
+        self.database.record_lot(&lot).await
+    }
+    
+    /// Match a sell from `position`'s book against open lots and return the
+    /// realized PnL. `proceeds_sol` is net of `fee_sol`.
+    pub async fn record_sell(
+        &self,
+        position: &Position,
+        tokens_sold: f64,
+        proceeds_sol: f64,
+        fee_sol: f64,
+    ) -> Result<f64, BotError> {
+        if tokens_sold <= 0.0 {
+            return Ok(0.0);
+        }
+        
+        let _guard = self.write_lock.lock().await;
+        let mut lots = self.database.get_open_lots(
+            &position.token_mint,
+            &position.strategy,
+            position.trader_address.as_deref(),
+        ).await?;
+        
+        if self.method == CostBasisMethod::AverageCost {
+            Self::apply_average_cost(&mut lots);
+        }
+        
+        let now = Utc::now();
+        let mut remaining = tokens_sold;
+        let mut realized = 0.0;
+        let mut lots_closed = false;
+        
+        for lot in lots.iter_mut() {
+            if remaining <= DUST_TOKENS {
+                break;
+            }
+            
+            let matched = remaining.min(lot.remaining_tokens);
+            let share_of_lot = matched / lot.remaining_tokens;
+            let share_of_sell = matched / tokens_sold;
+            
+            let cost = lot.remaining_cost_sol * share_of_lot;
+            let proceeds = proceeds_sol * share_of_sell;
+            let buy_fee = if lot.token_amount > 0.0 { lot.fee_sol * matched / lot.token_amount } else { 0.0 };
+            let pnl = proceeds - cost;
+            
+            self.database.record_lot_closure(&LotClosure {
+                id: uuid::Uuid::new_v4().to_string(),
+                lot_id: lot.id.clone(),
+                token_mint: lot.token_mint.clone(),
+                strategy: lot.strategy.clone(),
+                trader_address: lot.trader_address.clone(),
+                tokens: matched,
+                cost_sol: cost,
+                proceeds_sol: proceeds,
+                fee_sol: buy_fee + fee_sol * share_of_sell,
+                realized_pnl: pnl,
+                closed_at: now,
+            }).await?;
+            
+            lot.remaining_tokens -= matched;
+            lot.remaining_cost_sol -= cost;
+            if lot.remaining_tokens <= DUST_TOKENS {
+                lot.remaining_tokens = 0.0;
+                lot.remaining_cost_sol = 0.0;
+                lot.status = "closed".to_string();
+                lots_closed = true;
+            }
+            self.database.record_lot(lot).await?;
+            
+            remaining -= matched;
+            realized += pnl;
+        }
+        
+        if remaining > DUST_TOKENS {
+            warn!(
+                "Sell of {:.4} {} exceeded open lots by {:.4} tokens",
+                tokens_sold, position.token_mint, remaining
+            );
+        }
+        
+        debug!("Ledger realized {:.6} SOL on {}", realized, position.token_mint);
+        
+        if lots_closed {
+            self.refresh_performance().await?;
+        }
+        
+        Ok(realized)
+    }
+    
+    /// Fold lots closed since the last refresh into the performance metrics.
+    pub async fn refresh_performance(&self) -> Result<(), BotError> {
+        let added = self.performance.write().await.refresh(&self.database).await?;
+        if added > 0 {
+            debug!("Added {} closed trades to performance metrics", added);
+        }
+        Ok(())
+    }
+    
+    pub async fn performance(&self, slice: &MetricsSlice) -> Option<PerformanceMetrics> {
+        self.performance.read().await.metrics(slice)
+    }
+    
+    pub async fn daily_stats(&self) -> Vec<DailyStats> {
+        self.performance.read().await.daily_stats()
+    }
+    
+    // Spread the book's total cost evenly over its tokens before matching
+    fn apply_average_cost(lots: &mut [Lot]) {
+        let total_tokens: f64 = lots.iter().map(|l| l.remaining_tokens).sum();
+        let total_cost: f64 = lots.iter().map(|l| l.remaining_cost_sol).sum();
+        if total_tokens <= 0.0 {
+            return;
+        }
+        
+        let average = total_cost / total_tokens;
+        for lot in lots.iter_mut() {
+            lot.remaining_cost_sol = lot.remaining_tokens * average;
+        }
+    }
+    
+    pub async fn pnl_by(&self, group: PnlGroup) -> Result<Vec<PnlBreakdown>, BotError> {
+        self.database.get_pnl_breakdown(group).await
+    }
+}
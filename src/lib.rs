@@ -4,8 +4,24 @@ pub mod types;
 pub mod heaven_client;
 pub mod database;
 pub mod monitoring;
+pub mod ledger;
+pub mod positions;
+pub mod risk;
+pub mod rebalancer;
+pub mod sizing;
+pub mod exits;
 pub mod bot;
+pub mod launch_queue;
 pub mod sniper;
+pub mod strategy_registry;
+pub mod strategy;
+pub mod scripting;
+pub mod trader_stream;
+pub mod consensus;
+pub mod fade;
+pub mod creators;
+pub mod evaluation;
+pub mod discovery;
 pub mod copy_trader;
 pub mod bundler;
 
@@ -16,4 +32,5 @@ pub use copy_trader::CopyTraderBot;
 pub use bundler::BundlerBot;
 pub use config::BotConfig;
 pub use error::BotError;
+pub use strategy::{Strategy, StrategyEvent, StrategyIntent};
 pub use types::*;
@@ -0,0 +1,358 @@
+use crate::{
+    config::CostBasisMethod,
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::Database,
+    ledger::Ledger,
+    types::{Position, SniperStrategy},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use chrono::Utc;
+
+// Balances within this relative tolerance are treated as matching on recovery
+const RECONCILE_TOLERANCE: f64 = 0.001;
+
+// Remainders below this are rounding dust rather than an open position
+const DUST_TOKENS: f64 = 1e-9;
+
+/// Open positions across all bots, persisted to the `positions` table so
+/// they survive restarts.
+pub struct PositionManager {
+    database: Arc<Database>,
+    heaven_client: Arc<HeavenClient>,
+    ledger: Ledger,
+    positions: RwLock<HashMap<String, Position>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    pub restored: usize,
+    pub adjusted: usize,
+    pub closed: usize,
+}
+
+impl PositionManager {
+    pub fn new(
+        database: Arc<Database>,
+        heaven_client: Arc<HeavenClient>,
+        cost_basis: CostBasisMethod,
+    ) -> Self {
+        Self {
+            ledger: Ledger::new(database.clone(), cost_basis),
+            database,
+            heaven_client,
+            positions: RwLock::new(HashMap::new()),
+        }
+    }
+    
+    /// Rebuild open positions from the database and reconcile them against
+    /// the wallet's actual token balances.
+    pub async fn recover(&self) -> Result<RecoveryReport, BotError> {
+        // Load closed-trade history into the performance metrics
+        self.ledger.refresh_performance().await?;
+        
+        let stored = self.database.get_open_positions().await?;
+        let mut report = RecoveryReport::default();
+        
+        // Several positions (e.g. a snipe and a copy trade) can share a token
+        let mut by_token: HashMap<String, Vec<Position>> = HashMap::new();
+        for position in stored {
+            by_token.entry(position.token_mint.clone()).or_default().push(position);
+        }
+        
+        let mut positions = self.positions.write().await;
+        positions.clear();
+        
+        for (token_mint, mut token_positions) in by_token {
+            let recorded: f64 = token_positions.iter().map(|p| p.token_amount).sum();
+            let wallet_balance = match self.heaven_client.get_token_balance(&token_mint).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    // An unknown balance is not an empty one; reconcile on the next restart
+                    warn!("Failed to read {} balance, restoring positions unreconciled: {}", token_mint, e);
+                    for position in token_positions {
+                        report.restored += 1;
+                        positions.insert(position.id.clone(), position);
+                    }
+                    continue;
+                }
+            };
+            
+            if wallet_balance <= 0.0 {
+                // Sold or transferred while we were down; the proceeds are unknown
+                for mut position in token_positions {
+                    warn!("Position {} in {} no longer held, closing", position.id, token_mint);
+                    self.ledger.record_sell(&position, position.token_amount, 0.0, 0.0).await?;
+                    position.token_amount = 0.0;
+                    position.unrealized_pnl = 0.0;
+                    position.status = "closed".to_string();
+                    self.database.record_position(&position).await?;
+                    report.closed += 1;
+                }
+                continue;
+            }
+            
+            if recorded > 0.0 && wallet_balance < recorded * (1.0 - RECONCILE_TOLERANCE) {
+                // Scale positions down to what the wallet actually holds
+                let ratio = wallet_balance / recorded;
+                warn!(
+                    "Wallet holds {:.4} of {} but positions record {:.4}, scaling by {:.4}",
+                    wallet_balance, token_mint, recorded, ratio
+                );
+                for position in token_positions.iter_mut() {
+                    self.ledger.record_sell(position, position.token_amount * (1.0 - ratio), 0.0, 0.0).await?;
+                    position.token_amount *= ratio;
+                    position.initial_token_amount *= ratio;
+                    position.amount_sol *= ratio;
+                    self.database.record_position(position).await?;
+                    report.adjusted += 1;
+                }
+            } else if wallet_balance > recorded * (1.0 + RECONCILE_TOLERANCE) {
+                info!(
+                    "Wallet holds {:.4} of {}, {:.4} more than tracked positions; extra is left untracked",
+                    wallet_balance, token_mint, wallet_balance - recorded
+                );
+            }
+            
+            for position in token_positions {
+                report.restored += 1;
+                positions.insert(position.id.clone(), position);
+            }
+        }
+        
+        info!(
+            "Position recovery complete: {} restored, {} adjusted, {} closed",
+            report.restored, report.adjusted, report.closed
+        );
+        
+        Ok(report)
+    }
+    
+    /// Track a new position. `fee_sol` is the part of `amount_sol` paid in fees.
+    pub async fn open_position(&self, position: Position, fee_sol: f64) -> Result<(), BotError> {
+        self.database.record_position(&position).await?;
+        self.ledger.record_buy(&position, fee_sol).await?;
+        
+        info!(
+            "Opened position {} in {} ({:.4} SOL, {} source)",
+            position.id, position.token_mint, position.amount_sol, position.source
+        );
+        self.positions.write().await.insert(position.id.clone(), position);
+        
+        Ok(())
+    }
+    
+    pub async fn update_price(&self, position_id: &str, price: f64) -> Result<Option<Position>, BotError> {
+        let updated = {
+            let mut positions = self.positions.write().await;
+            let Some(position) = positions.get_mut(position_id) else {
+                return Ok(None);
+            };
+            
+            position.current_price = price;
+            position.high_water_price = position.high_water_price.max(price);
+            position.unrealized_pnl = position.token_amount * price - position.amount_sol;
+            position.clone()
+        };
+        
+        self.database.record_position(&updated).await?;
+        Ok(Some(updated))
+    }
+    
+    /// Record a partial or full exit. The sold share of the cost basis is
+    /// realized against `sol_received` (net of `fee_sol`); the position closes
+    /// once no tokens remain. The sell is also matched in the PnL ledger.
+    pub async fn reduce_position(
+        &self,
+        position_id: &str,
+        tokens_sold: f64,
+        sol_received: f64,
+        fee_sol: f64,
+    ) -> Result<Option<Position>, BotError> {
+        let (before, updated) = {
+            let mut positions = self.positions.write().await;
+            let Some(position) = positions.get_mut(position_id) else {
+                return Ok(None);
+            };
+            let before = position.clone();
+            
+            let sold = tokens_sold.min(position.token_amount);
+            let fraction = if position.token_amount > 0.0 { sold / position.token_amount } else { 1.0 };
+            let cost_sold = position.amount_sol * fraction;
+            
+            position.realized_pnl += sol_received - cost_sold;
+            position.amount_sol -= cost_sold;
+            position.token_amount -= sold;
+            position.unrealized_pnl = position.token_amount * position.current_price - position.amount_sol;
+            
+            if position.token_amount <= DUST_TOKENS {
+                position.token_amount = 0.0;
+                position.amount_sol = 0.0;
+                position.unrealized_pnl = 0.0;
+                position.status = "closed".to_string();
+            }
+            
+            let updated = position.clone();
+            if updated.status == "closed" {
+                positions.remove(position_id);
+            }
+            (before, updated)
+        };
+        
+        self.database.record_position(&updated).await?;
+        self.ledger.record_sell(&before, before.token_amount - updated.token_amount, sol_received, fee_sol).await?;
+        
+        if updated.status == "closed" {
+            info!(
+                "Closed position {} in {} (realized {:.4} SOL)",
+                updated.id, updated.token_mint, updated.realized_pnl
+            );
+        }
+        
+        Ok(Some(updated))
+    }
+    
+    pub async fn close_position(
+        &self,
+        position_id: &str,
+        sol_received: f64,
+        fee_sol: f64,
+    ) -> Result<Option<Position>, BotError> {
+        let tokens = match self.positions.read().await.get(position_id) {
+            Some(position) => position.token_amount,
+            None => return Ok(None),
+        };
+        
+        self.reduce_position(position_id, tokens, sol_received, fee_sol).await
+    }
+    
+    /// Mark take-profit ladder levels up to and including `level` as filled.
+    pub async fn record_take_profit(&self, position_id: &str, level: usize) -> Result<(), BotError> {
+        let updated = {
+            let mut positions = self.positions.write().await;
+            let Some(position) = positions.get_mut(position_id) else {
+                return Ok(());
+            };
+            
+            position.take_profits_hit = position.take_profits_hit.max(level as u32 + 1);
+            position.clone()
+        };
+        
+        self.database.record_position(&updated).await
+    }
+    
+    /// Store the stop-loss and take-profit prices an exit plan currently implies.
+    pub async fn set_exit_levels(
+        &self,
+        position_id: &str,
+        stop_loss: f64,
+        take_profit: Option<f64>,
+    ) -> Result<(), BotError> {
+        let updated = {
+            let mut positions = self.positions.write().await;
+            let Some(position) = positions.get_mut(position_id) else {
+                return Ok(());
+            };
+            
+            position.stop_loss = Some(stop_loss);
+            position.take_profit = take_profit;
+            position.clone()
+        };
+        
+        self.database.record_position(&updated).await
+    }
+    
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+    
+    pub async fn get_position(&self, position_id: &str) -> Option<Position> {
+        self.positions.read().await.get(position_id).cloned()
+    }
+    
+    pub async fn open_positions(&self) -> Vec<Position> {
+        self.positions.read().await.values().cloned().collect()
+    }
+    
+    pub async fn open_positions_by_source(&self, source: &str) -> Vec<Position> {
+        self.positions.read().await
+            .values()
+            .filter(|p| p.source == source)
+            .cloned()
+            .collect()
+    }
+    
+    pub async fn refresh_prices(&self) -> Result<(), BotError> {
+        let ids: Vec<(String, String)> = self.positions.read().await
+            .values()
+            .map(|p| (p.id.clone(), p.token_mint.clone()))
+            .collect();
+        
+        for (position_id, token_mint) in ids {
+            match self.heaven_client.get_token_price(&token_mint).await {
+                Ok(price) => {
+                    self.update_price(&position_id, price).await?;
+                }
+                Err(e) => warn!("Failed to refresh price for position {}: {}", position_id, e),
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Marked value of open positions per token.
+    pub async fn exposure_by_token(&self) -> HashMap<String, f64> {
+        let mut exposure = HashMap::new();
+        for position in self.positions.read().await.values() {
+            *exposure.entry(position.token_mint.clone()).or_insert(0.0) += position.token_amount * position.current_price;
+        }
+        exposure
+    }
+    
+    pub async fn market_value(&self) -> f64 {
+        self.positions.read().await
+            .values()
+            .map(|p| p.token_amount * p.current_price)
+            .sum()
+    }
+    
+    pub async fn total_unrealized_pnl(&self) -> f64 {
+        self.positions.read().await.values().map(|p| p.unrealized_pnl).sum()
+    }
+}
+
+/// Build a new open position from an executed buy.
+pub fn new_position(
+    id: String,
+    token_mint: &str,
+    source: &str,
+    strategy: SniperStrategy,
+    entry_price: f64,
+    amount_sol: f64,
+    token_amount: f64,
+) -> Position {
+    Position {
+        id,
+        token_mint: token_mint.to_string(),
+        entry_price,
+        current_price: entry_price,
+        amount_sol,
+        token_amount,
+        unrealized_pnl: 0.0,
+        entry_time: Utc::now(),
+        strategy,
+        stop_loss: None,
+        take_profit: None,
+        source: source.to_string(),
+        trader_address: None,
+        realized_pnl: 0.0,
+        status: "open".to_string(),
+        initial_token_amount: token_amount,
+        high_water_price: entry_price,
+        take_profits_hit: 0,
+    }
+}
+
@@ -0,0 +1,313 @@
+use crate::{
+    config::{BotConfig, RebalanceConfig},
+    error::BotError,
+    exits::{ExitReason, ExitSignal, ExitSlicer},
+    heaven_client::HeavenClient,
+    database::Database,
+    monitoring::Metrics,
+    positions::{new_position, PositionManager},
+    risk::RiskEngine,
+    types::{Position, SniperStrategy, Trade},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    instruction::Instruction,
+    compute_budget::ComputeBudgetInstruction,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn, debug};
+use chrono::Utc;
+
+/// Strategy tag for trades placed by the rebalancer
+pub const REBALANCE_TAG: &str = "rebalance";
+
+/// Keeps the split between SOL and open positions within the
+/// `[trading.rebalance]` targets by trimming positions.
+pub struct Rebalancer {
+    config: BotConfig,
+    rpc_client: Arc<RpcClient>,
+    heaven_client: Arc<HeavenClient>,
+    database: Arc<Database>,
+    metrics: Arc<Metrics>,
+    positions: Arc<PositionManager>,
+    risk: Arc<RiskEngine>,
+    wallet: Arc<Keypair>,
+    // Trims are worked off in impact-bounded slices, keyed by token mint
+    exit_slicer: RwLock<ExitSlicer>,
+}
+
+/// A planned sale of part of our holding in one token.
+#[derive(Debug, Clone)]
+pub struct Trim {
+    pub token_mint: String,
+    pub sell_sol: f64,
+    /// Share of the token holding to sell
+    pub fraction: f64,
+}
+
+impl Rebalancer {
+    pub fn new(
+        config: BotConfig,
+        rpc_client: Arc<RpcClient>,
+        heaven_client: Arc<HeavenClient>,
+        database: Arc<Database>,
+        metrics: Arc<Metrics>,
+        positions: Arc<PositionManager>,
+        risk: Arc<RiskEngine>,
+        wallet: Arc<Keypair>,
+    ) -> Self {
+        let exit_slicer = RwLock::new(ExitSlicer::new(config.trading.execution.twap_interval_secs));
+        
+        Self {
+            config,
+            rpc_client,
+            heaven_client,
+            database,
+            metrics,
+            positions,
+            risk,
+            wallet,
+            exit_slicer,
+        }
+    }
+    
+    pub async fn run(&self, is_running: Arc<RwLock<bool>>) -> Result<(), BotError> {
+        let mut interval = tokio::time::interval(
+            std::time::Duration::from_secs(self.config.trading.rebalance.interval_secs)
+        );
+        
+        while *is_running.read().await {
+            interval.tick().await;
+            
+            // Owners are already selling everything
+            if self.risk.is_flattening().await {
+                continue;
+            }
+            
+            if let Err(e) = self.rebalance().await {
+                warn!("Rebalance failed: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Plan and execute one rebalancing pass.
+    pub async fn rebalance(&self) -> Result<(), BotError> {
+        self.positions.refresh_prices().await?;
+        let sol_balance = self.heaven_client.get_sol_balance().await?;
+        let exposure = self.positions.exposure_by_token().await;
+        
+        let trims = plan_trims(&self.config.trading.rebalance, sol_balance, &exposure);
+        let mut signals: HashMap<String, ExitSignal> = trims.iter()
+            .map(|trim| (trim.token_mint.clone(), ExitSignal {
+                fraction: trim.fraction,
+                reason: ExitReason::Rebalance,
+            }))
+            .collect();
+        for trim in &trims {
+            info!(
+                "Rebalancing: selling {:.0}% of {} (~{:.4} SOL)",
+                trim.fraction * 100.0, trim.token_mint, trim.sell_sol
+            );
+        }
+        
+        // Earlier trims still being sold off continue alongside new ones
+        let mut token_mints: Vec<String> = trims.into_iter().map(|trim| trim.token_mint).collect();
+        for token_mint in self.exit_slicer.read().await.pending_ids() {
+            if !token_mints.contains(&token_mint) {
+                token_mints.push(token_mint);
+            }
+        }
+        if token_mints.is_empty() {
+            debug!("Portfolio within rebalance targets");
+            return Ok(());
+        }
+        
+        for token_mint in token_mints {
+            let signal = signals.remove(&token_mint);
+            if let Err(e) = self.sell_next_slice(&token_mint, signal.as_ref()).await {
+                warn!("Failed to trim {}: {}", token_mint, e);
+                self.metrics.record_failed_trade(0.0).await;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Sell the slice of a token's trim that is due now, bounded by exit price impact.
+    async fn sell_next_slice(&self, token_mint: &str, signal: Option<&ExitSignal>) -> Result<(), BotError> {
+        let positions: Vec<Position> = self.positions.open_positions().await
+            .into_iter()
+            .filter(|p| p.token_mint == token_mint)
+            .collect();
+        let held: f64 = positions.iter().map(|p| p.token_amount).sum();
+        
+        // Never sell more than the wallet actually holds
+        let token_balance = self.heaven_client.get_token_balance(token_mint).await?;
+        let holding = new_position(
+            token_mint.to_string(),
+            token_mint,
+            REBALANCE_TAG,
+            SniperStrategy::Custom(REBALANCE_TAG.to_string()),
+            0.0,
+            0.0,
+            held.min(token_balance),
+        );
+        if holding.token_amount <= 0.0 {
+            self.exit_slicer.write().await.forget(token_mint);
+            return Ok(());
+        }
+        
+        let max_exit_impact = self.config.trading.execution.max_exit_impact;
+        let max_tokens = self.heaven_client.max_sell_for_impact(token_mint, max_exit_impact).await?;
+        let Some(slice) = self.exit_slicer.write().await.next_slice(&holding, signal, max_tokens) else {
+            return Ok(());
+        };
+        
+        let sold = self.execute_sell(token_mint, slice.tokens, &positions, held).await;
+        let mut exit_slicer = self.exit_slicer.write().await;
+        match &sold {
+            Ok(()) if slice.closes => exit_slicer.forget(token_mint),
+            Ok(()) => exit_slicer.record_sold(token_mint, slice.tokens),
+            // Start over from the next plan rather than retrying a stale slice
+            Err(_) => exit_slicer.forget(token_mint),
+        }
+        
+        sold
+    }
+    
+    async fn execute_sell(
+        &self,
+        token_mint: &str,
+        token_amount: f64,
+        positions: &[Position],
+        held: f64,
+    ) -> Result<(), BotError> {
+        let slippage = self.config.heaven.max_slippage;
+        let quote = self.heaven_client.get_sell_quote(token_mint, token_amount, slippage).await?;
+        let sell_ix = self.heaven_client.create_sell_instruction(
+            token_mint,
+            token_amount,
+            quote.sol_amount,
+            &self.wallet.pubkey(),
+        ).await?;
+        
+        let signature = self.send_instructions(vec![sell_ix]).await?;
+        
+        let mut trade = Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            token_mint: token_mint.to_string(),
+            trade_type: "sell".to_string(),
+            amount_sol: quote.sol_amount,
+            token_amount,
+            price: quote.price,
+            slippage,
+            strategy: SniperStrategy::Custom(REBALANCE_TAG.to_string()),
+            timestamp: Utc::now(),
+            status: "pending".to_string(),
+            transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: None,
+        };
+        let settled = self.heaven_client.settle_trade(&mut trade, &signature.to_string()).await;
+        self.database.record_trade(&trade).await?;
+        settled?;
+        self.metrics.record_successful_trade(trade.amount_sol).await;
+        self.metrics.increment_counter("rebalance_trades", 1).await;
+        
+        // Every position in the token gives up the same share, so each book realizes its own PnL
+        for position in positions {
+            let share = if held > 0.0 { position.token_amount / held } else { 0.0 };
+            self.positions.reduce_position(
+                &position.id,
+                trade.token_amount * share,
+                trade.sol_received * share,
+                trade.fee_sol * share,
+            ).await?;
+        }
+        
+        info!(
+            "Rebalance sold {:.4} {} for {:.4} SOL",
+            trade.token_amount, token_mint, trade.sol_received
+        );
+        
+        Ok(())
+    }
+    
+    async fn send_instructions(&self, instructions: Vec<Instruction>) -> Result<Signature, BotError> {
+        let mut all_instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(self.config.heaven.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.heaven.compute_unit_price),
+        ];
+        all_instructions.extend(instructions);
+        
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &all_instructions,
+            Some(&self.wallet.pubkey()),
+            &[&*self.wallet],
+            recent_blockhash,
+        );
+        
+        Ok(self.rpc_client.send_and_confirm_transaction(&transaction)?)
+    }
+}
+
+/// Work out which tokens to trim so that no token exceeds its share of
+/// equity, positions stay under their total share, and the SOL reserve is
+/// topped up. `exposure` is the marked value of each token held.
+pub fn plan_trims(
+    targets: &RebalanceConfig,
+    sol_balance: f64,
+    exposure: &HashMap<String, f64>,
+) -> Vec<Trim> {
+    let invested: f64 = exposure.values().sum();
+    let equity = sol_balance + invested;
+    if equity <= 0.0 || invested <= 0.0 {
+        return Vec::new();
+    }
+    
+    // Oversized tokens first
+    let token_cap = equity * targets.max_token_share;
+    let mut sells: HashMap<&str, f64> = exposure.iter()
+        .map(|(mint, value)| (mint.as_str(), (value - token_cap).max(0.0)))
+        .collect();
+    let trimmed: f64 = sells.values().sum();
+    
+    // Then whatever is still needed for the total cap or the fee reserve,
+    // taken from every token in proportion to what is left of it
+    let remaining = invested - trimmed;
+    let over_total = (remaining - equity * targets.max_total_in_positions).max(0.0);
+    let reserve_shortfall = (targets.min_sol_reserve - (sol_balance + trimmed)).max(0.0);
+    let extra = over_total.max(reserve_shortfall).min(remaining);
+    if extra > 0.0 && remaining > 0.0 {
+        for (mint, value) in exposure {
+            let sell = sells.entry(mint.as_str()).or_insert(0.0);
+            *sell += (value - *sell) / remaining * extra;
+        }
+    }
+    
+    let mut trims: Vec<Trim> = sells.into_iter()
+        .filter(|(_, sell_sol)| *sell_sol >= targets.min_trade_sol)
+        .filter_map(|(mint, sell_sol)| {
+            let value = exposure.get(mint).copied().unwrap_or(0.0);
+            (value > 0.0).then(|| Trim {
+                token_mint: mint.to_string(),
+                sell_sol,
+                fraction: (sell_sol / value).min(1.0),
+            })
+        })
+        .collect();
+    
+    // Largest trims first, so the reserve recovers early if later ones fail
+    trims.sort_by(|a, b| b.sell_sol.total_cmp(&a.sell_sol));
+    trims
+}
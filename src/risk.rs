@@ -0,0 +1,475 @@
+use crate::{
+    config::TradingConfig,
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::Database,
+    monitoring::{AlertLevel, Metrics},
+    positions::PositionManager,
+    types::{BotEvent, EventSeverity, EventType},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn, error, debug};
+use chrono::{DateTime, Utc};
+
+// Risk events buffered for slow subscribers
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+// Keys of the state that survives restarts in the risk_state table
+const HALT_KEY: &str = "halt";
+const PEAK_EQUITY_KEY: &str = "peak_equity";
+
+/// Portfolio-level checks every buy has to pass before it is sent.
+pub struct RiskEngine {
+    config: TradingConfig,
+    database: Arc<Database>,
+    heaven_client: Arc<HeavenClient>,
+    positions: Arc<PositionManager>,
+    metrics: Arc<Metrics>,
+    // Approved buys that have not opened a position yet
+    pending: Arc<StdMutex<HashMap<u64, PendingBuy>>>,
+    next_permit_id: AtomicU64,
+    halt: RwLock<Option<Halt>>,
+    consecutive_failures: AtomicU32,
+    rpc_results: StdMutex<VecDeque<bool>>,
+    peak_equity: StdMutex<Option<f64>>,
+    events: broadcast::Sender<BotEvent>,
+}
+
+/// Circuit breakers that halt new entries.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CircuitBreaker {
+    ConsecutiveFailures,
+    RpcErrorRate,
+    Drawdown,
+    BalanceFloor,
+    KillSwitch,
+}
+
+/// Why and when trading was halted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Halt {
+    pub breaker: CircuitBreaker,
+    pub reason: String,
+    pub tripped_at: DateTime<Utc>,
+    /// Open positions are being sold off
+    pub flatten: bool,
+}
+
+#[derive(Debug, Clone)]
+struct PendingBuy {
+    token_mint: String,
+    amount_sol: f64,
+}
+
+/// Why a buy was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskRejection {
+    MaxConcurrentTrades { open: usize, limit: usize },
+    DailyTradeLimit { trades: u64, limit: usize },
+    DailyLossLimit { pnl: f64, limit: f64 },
+    TokenExposure { token_mint: String, exposure: f64, limit: f64 },
+    PositionSize { amount_sol: f64, limit: f64 },
+    NoEquity,
+    Halted(CircuitBreaker),
+    /// Limits could not be evaluated, so the buy is refused
+    Unavailable(String),
+}
+
+impl fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskRejection::MaxConcurrentTrades { open, limit } => {
+                write!(f, "{} open positions, limit is {}", open, limit)
+            }
+            RiskRejection::DailyTradeLimit { trades, limit } => {
+                write!(f, "{} buys today, limit is {}", trades, limit)
+            }
+            RiskRejection::DailyLossLimit { pnl, limit } => {
+                write!(f, "daily PnL {:.4} SOL is past the -{:.4} SOL loss limit", pnl, limit)
+            }
+            RiskRejection::TokenExposure { token_mint, exposure, limit } => {
+                write!(f, "exposure to {} would be {:.4} SOL, limit is {:.4} SOL", token_mint, exposure, limit)
+            }
+            RiskRejection::PositionSize { amount_sol, limit } => {
+                write!(f, "buy of {:.4} SOL exceeds the {:.4} SOL position limit", amount_sol, limit)
+            }
+            RiskRejection::NoEquity => write!(f, "no equity available"),
+            RiskRejection::Halted(breaker) => write!(f, "trading halted by {:?} circuit breaker", breaker),
+            RiskRejection::Unavailable(reason) => write!(f, "risk state unavailable: {}", reason),
+        }
+    }
+}
+
+impl From<RiskRejection> for BotError {
+    fn from(rejection: RiskRejection) -> Self {
+        BotError::RiskLimit(rejection.to_string())
+    }
+}
+
+/// Reservation for an approved buy. It counts against the limits until
+/// dropped, which should happen once the resulting position is open.
+pub struct RiskPermit {
+    id: u64,
+    pending: Arc<StdMutex<HashMap<u64, PendingBuy>>>,
+}
+
+impl Drop for RiskPermit {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&self.id);
+        }
+    }
+}
+
+impl RiskEngine {
+    pub fn new(
+        config: TradingConfig,
+        database: Arc<Database>,
+        heaven_client: Arc<HeavenClient>,
+        positions: Arc<PositionManager>,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        
+        Self {
+            config,
+            database,
+            heaven_client,
+            positions,
+            metrics,
+            pending: Arc::new(StdMutex::new(HashMap::new())),
+            next_permit_id: AtomicU64::new(0),
+            halt: RwLock::new(None),
+            consecutive_failures: AtomicU32::new(0),
+            rpc_results: StdMutex::new(VecDeque::new()),
+            peak_equity: StdMutex::new(None),
+            events,
+        }
+    }
+    
+    /// `RiskLimitExceeded` events emitted when a circuit breaker trips, and
+    /// anything else published through `emit`.
+    pub fn subscribe(&self) -> broadcast::Receiver<BotEvent> {
+        self.events.subscribe()
+    }
+    
+    /// Restore a halt and the equity peak from before a restart. A halt stays
+    /// in force until `resume` is called.
+    pub async fn load(&self) -> Result<(), BotError> {
+        if let Some(peak) = self.database.get_risk_state(PEAK_EQUITY_KEY).await? {
+            match peak.parse::<f64>() {
+                Ok(peak) => *lock(&self.peak_equity) = Some(peak),
+                Err(e) => warn!("Ignoring stored peak equity {}: {}", peak, e),
+            }
+        }
+        
+        let Some(stored) = self.database.get_risk_state(HALT_KEY).await? else {
+            return Ok(());
+        };
+        let halt: Halt = serde_json::from_str(&stored)
+            .map_err(|e| BotError::Validation(format!("Invalid stored halt: {}", e)))?;
+        
+        warn!(
+            "Trading is still halted by {:?} since {}: {}. Resume explicitly to trade again",
+            halt.breaker, halt.tripped_at, halt.reason
+        );
+        self.metrics.set_gauge("trading_halted", 1.0).await;
+        *self.halt.write().await = Some(halt);
+        
+        Ok(())
+    }
+    
+    pub async fn halt(&self) -> Option<Halt> {
+        self.halt.read().await.clone()
+    }
+    
+    /// Whether open positions should be sold off by their owners.
+    pub async fn is_flattening(&self) -> bool {
+        self.halt.read().await.as_ref().map(|h| h.flatten).unwrap_or(false)
+    }
+    
+    /// Halt trading and sell every open position.
+    pub async fn kill(&self, reason: &str) {
+        self.trip(CircuitBreaker::KillSwitch, reason.to_string(), true).await;
+    }
+    
+    /// Clear a halt and reset the breaker state it was built from.
+    pub async fn resume(&self) {
+        let Some(halt) = self.halt.write().await.take() else {
+            return;
+        };
+        
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        lock(&self.rpc_results).clear();
+        *lock(&self.peak_equity) = None;
+        self.metrics.set_gauge("trading_halted", 0.0).await;
+        for key in [HALT_KEY, PEAK_EQUITY_KEY] {
+            if let Err(e) = self.database.clear_risk_state(key).await {
+                warn!("Failed to clear stored {}: {}", key, e);
+            }
+        }
+        
+        info!("Trading resumed after {:?} halt: {}", halt.breaker, halt.reason);
+        self.emit(EventType::Info, EventSeverity::Info, serde_json::json!({
+            "action": "resume",
+            "breaker": halt.breaker,
+        }));
+    }
+    
+    pub async fn record_trade_result(&self, success: bool) {
+        if success {
+            self.consecutive_failures.store(0, Ordering::Relaxed);
+            return;
+        }
+        
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let limit = self.config.circuit_breakers.max_consecutive_failures;
+        if limit > 0 && failures >= limit {
+            self.trip(
+                CircuitBreaker::ConsecutiveFailures,
+                format!("{} trades failed in a row", failures),
+                false,
+            ).await;
+        }
+    }
+    
+    pub async fn record_rpc_result(&self, success: bool) {
+        let window = self.config.circuit_breakers.rpc_window;
+        if window == 0 {
+            return;
+        }
+        
+        let error_rate = {
+            let mut results = lock(&self.rpc_results);
+            results.push_back(success);
+            while results.len() > window {
+                results.pop_front();
+            }
+            
+            // Too few samples to judge yet
+            if results.len() < window {
+                return;
+            }
+            results.iter().filter(|ok| !**ok).count() as f64 / window as f64
+        };
+        
+        if error_rate > self.config.circuit_breakers.max_rpc_error_rate {
+            self.trip(
+                CircuitBreaker::RpcErrorRate,
+                format!("{:.0}% of the last {} RPC calls failed", error_rate * 100.0, window),
+                false,
+            ).await;
+        }
+    }
+    
+    /// Check the balance floor and drawdown from peak equity.
+    pub async fn record_portfolio(&self, sol_balance: f64, equity: f64) {
+        let breakers = &self.config.circuit_breakers;
+        
+        if sol_balance < breakers.min_sol_balance {
+            self.trip(
+                CircuitBreaker::BalanceFloor,
+                format!("SOL balance {:.4} is below the {:.4} floor", sol_balance, breakers.min_sol_balance),
+                false,
+            ).await;
+        }
+        
+        let (drawdown, new_peak) = {
+            let mut peak = lock(&self.peak_equity);
+            let new_peak = peak.map_or(true, |p| equity > p).then_some(equity);
+            let peak = peak.get_or_insert(equity);
+            *peak = peak.max(equity);
+            let drawdown = if *peak > 0.0 { (*peak - equity) / *peak } else { 0.0 };
+            (drawdown, new_peak)
+        };
+        if let Some(peak) = new_peak {
+            if let Err(e) = self.database.set_risk_state(PEAK_EQUITY_KEY, &peak.to_string()).await {
+                warn!("Failed to store peak equity: {}", e);
+            }
+        }
+        self.metrics.set_gauge("equity_drawdown", drawdown).await;
+        
+        if drawdown >= breakers.max_drawdown {
+            self.trip(
+                CircuitBreaker::Drawdown,
+                format!("equity is {:.1}% below its peak", drawdown * 100.0),
+                false,
+            ).await;
+        }
+    }
+    
+    // The first trip is kept until resume, though a kill switch can still add flattening
+    async fn trip(&self, breaker: CircuitBreaker, reason: String, flatten: bool) {
+        let mut halt = self.halt.write().await;
+        match halt.as_mut() {
+            Some(current) if current.flatten || !flatten => return,
+            Some(current) => current.flatten = true,
+            None => {
+                *halt = Some(Halt {
+                    breaker,
+                    reason: reason.clone(),
+                    tripped_at: Utc::now(),
+                    flatten,
+                });
+            }
+        }
+        let stored = halt.as_ref().map(serde_json::to_string);
+        drop(halt);
+        
+        // A halt outlives restarts until it is explicitly resumed
+        match stored {
+            Some(Ok(stored)) => {
+                if let Err(e) = self.database.set_risk_state(HALT_KEY, &stored).await {
+                    error!("Failed to store {:?} halt: {}", breaker, e);
+                }
+            }
+            Some(Err(e)) => error!("Failed to serialize {:?} halt: {}", breaker, e),
+            None => {}
+        }
+        
+        warn!("Circuit breaker {:?} tripped: {}", breaker, reason);
+        self.metrics.set_gauge("trading_halted", 1.0).await;
+        self.metrics.increment_counter("circuit_breaker_trips", 1).await;
+        self.metrics.create_alert(
+            AlertLevel::Critical,
+            &format!("Trading halted by {:?}: {}", breaker, reason),
+        ).await;
+        
+        self.emit(EventType::RiskLimitExceeded, EventSeverity::Critical, serde_json::json!({
+            "breaker": breaker,
+            "reason": reason,
+            "flatten": flatten,
+        }));
+    }
+    
+    /// Publish an event to everyone subscribed to risk events.
+    pub fn emit(&self, event_type: EventType, severity: EventSeverity, data: serde_json::Value) {
+        // Nobody listening is fine
+        let _ = self.events.send(BotEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            timestamp: Utc::now(),
+            data,
+            severity,
+        });
+    }
+    
+    /// Check a buy of `amount_sol` into `token_mint` from `source` against
+    /// all portfolio limits and reserve it if it passes.
+    pub async fn check_buy(
+        &self,
+        source: &str,
+        token_mint: &str,
+        amount_sol: f64,
+    ) -> Result<RiskPermit, RiskRejection> {
+        let verdict = self.evaluate(token_mint, amount_sol).await
+            .unwrap_or_else(|e| Err(RiskRejection::Unavailable(e.to_string())));
+        
+        match verdict {
+            Ok(()) => {
+                let id = self.next_permit_id.fetch_add(1, Ordering::Relaxed);
+                self.lock_pending().insert(id, PendingBuy {
+                    token_mint: token_mint.to_string(),
+                    amount_sol,
+                });
+                debug!("Risk approved {} buy of {:.4} SOL in {}", source, amount_sol, token_mint);
+                
+                Ok(RiskPermit {
+                    id,
+                    pending: self.pending.clone(),
+                })
+            }
+            Err(rejection) => {
+                info!("Risk rejected {} buy of {:.4} SOL in {}: {}", source, amount_sol, token_mint, rejection);
+                self.metrics.increment_counter("risk_rejections", 1).await;
+                Err(rejection)
+            }
+        }
+    }
+    
+    async fn evaluate(&self, token_mint: &str, amount_sol: f64) -> Result<Result<(), RiskRejection>, BotError> {
+        if let Some(halt) = self.halt.read().await.as_ref() {
+            return Ok(Err(RiskRejection::Halted(halt.breaker)));
+        }
+        
+        let pending: Vec<PendingBuy> = self.lock_pending().values().cloned().collect();
+        
+        // Concurrency
+        let open = self.positions.open_positions().await.len() + pending.len();
+        if open >= self.config.max_concurrent_trades {
+            return Ok(Err(RiskRejection::MaxConcurrentTrades {
+                open,
+                limit: self.config.max_concurrent_trades,
+            }));
+        }
+        
+        // Daily trade count
+        let start_of_day = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        let trades = self.database.count_buys_since(start_of_day).await? + pending.len() as u64;
+        if trades >= self.config.max_daily_trades as u64 {
+            return Ok(Err(RiskRejection::DailyTradeLimit {
+                trades,
+                limit: self.config.max_daily_trades,
+            }));
+        }
+        
+        // Daily realized plus unrealized loss
+        let pnl = self.database.get_daily_pnl().await?;
+        if pnl <= -self.config.max_daily_loss_sol {
+            return Ok(Err(RiskRejection::DailyLossLimit {
+                pnl,
+                limit: self.config.max_daily_loss_sol,
+            }));
+        }
+        
+        // Pending buys have not left the wallet yet, so they are still in the SOL balance
+        let sol_balance = self.heaven_client.get_sol_balance().await;
+        self.record_rpc_result(sol_balance.is_ok()).await;
+        let sol_balance = sol_balance?;
+        let equity = sol_balance + self.positions.market_value().await;
+        if equity <= 0.0 {
+            return Ok(Err(RiskRejection::NoEquity));
+        }
+        
+        // Position size
+        let size_limit = equity * self.config.max_position_fraction;
+        if amount_sol > size_limit {
+            return Ok(Err(RiskRejection::PositionSize {
+                amount_sol,
+                limit: size_limit,
+            }));
+        }
+        
+        // Per-token exposure, including buys still in flight
+        let held = self.positions.exposure_by_token().await.get(token_mint).copied().unwrap_or(0.0);
+        let in_flight: f64 = pending.iter()
+            .filter(|p| p.token_mint == token_mint)
+            .map(|p| p.amount_sol)
+            .sum();
+        let exposure = held + in_flight + amount_sol;
+        let exposure_limit = equity * self.config.max_token_exposure;
+        if exposure > exposure_limit {
+            return Ok(Err(RiskRejection::TokenExposure {
+                token_mint: token_mint.to_string(),
+                exposure,
+                limit: exposure_limit,
+            }));
+        }
+        
+        Ok(Ok(()))
+    }
+    
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, HashMap<u64, PendingBuy>> {
+        lock(&self.pending)
+    }
+}
+
+// Poisoned locks only guard plain data, so keep using them
+fn lock<T>(mutex: &StdMutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
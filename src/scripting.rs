@@ -0,0 +1,297 @@
+use crate::{
+    config::ScriptingConfig,
+    error::BotError,
+    types::{TokenLaunch, MarketData, Position, PoolState},
+};
+use rhai::{Dynamic, Engine, Scope, AST};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+use tokio::sync::RwLock;
+use tracing::{info, warn, error, debug};
+use chrono::Utc;
+
+/// Rule scripts loaded from `scripting.scripts_dir`.
+///
+/// Each `*.rhai` file may define any of these hooks:
+/// - `on_launch(launch, pool)` - sniper entry filter
+/// - `on_copy(market, trader, side, amount_sol)` - copy trader entry filter
+/// - `on_exit(position, market)` - exit rule for open positions
+///
+/// Hooks return `true`/`"allow"` to approve, `false`/`"reject"` to veto, or
+/// `()` to abstain. For `on_exit`, approving means "close the position".
+pub struct ScriptEngine {
+    config: ScriptingConfig,
+    engine: Engine,
+    scripts: RwLock<HashMap<PathBuf, LoadedScript>>,
+    last_reload: RwLock<Option<Instant>>,
+}
+
+struct LoadedScript {
+    name: String,
+    modified: SystemTime,
+    ast: AST,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScriptDecision {
+    Allow,
+    Reject,
+    Abstain,
+}
+
+impl ScriptDecision {
+    fn from_dynamic(value: Dynamic) -> Result<Self, String> {
+        if value.is_unit() {
+            return Ok(ScriptDecision::Abstain);
+        }
+        
+        if let Some(flag) = value.clone().try_cast::<bool>() {
+            return Ok(if flag { ScriptDecision::Allow } else { ScriptDecision::Reject });
+        }
+        
+        if let Some(text) = value.clone().try_cast::<String>() {
+            return match text.to_lowercase().as_str() {
+                "allow" | "buy" | "sell" | "exit" => Ok(ScriptDecision::Allow),
+                "reject" | "skip" | "hold" => Ok(ScriptDecision::Reject),
+                "abstain" | "" => Ok(ScriptDecision::Abstain),
+                other => Err(format!("Unknown decision '{}'", other)),
+            };
+        }
+        
+        Err(format!("Unsupported return type '{}'", value.type_name()))
+    }
+    
+    // Any veto wins, otherwise any approval, otherwise no opinion
+    fn combine(self, other: ScriptDecision) -> ScriptDecision {
+        match (self, other) {
+            (ScriptDecision::Reject, _) | (_, ScriptDecision::Reject) => ScriptDecision::Reject,
+            (ScriptDecision::Allow, _) | (_, ScriptDecision::Allow) => ScriptDecision::Allow,
+            _ => ScriptDecision::Abstain,
+        }
+    }
+}
+
+impl ScriptEngine {
+    pub fn new(config: &ScriptingConfig) -> Result<Self, BotError> {
+        let mut engine = Engine::new();
+        
+        // Per-invocation CPU budget and resource limits
+        engine.set_max_operations(config.max_operations);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(4096);
+        engine.set_max_array_size(1024);
+        engine.set_max_map_size(256);
+        
+        Self::register_types(&mut engine);
+        
+        Ok(Self {
+            config: config.clone(),
+            engine,
+            scripts: RwLock::new(HashMap::new()),
+            last_reload: RwLock::new(None),
+        })
+    }
+    
+    // Only getters are registered so scripts cannot mutate bot state
+    fn register_types(engine: &mut Engine) {
+        engine.register_type_with_name::<TokenLaunch>("TokenLaunch")
+            .register_get("token_mint", |l: &mut TokenLaunch| l.token_mint.clone())
+            .register_get("token_name", |l: &mut TokenLaunch| l.token_name.clone())
+            .register_get("token_symbol", |l: &mut TokenLaunch| l.token_symbol.clone())
+            .register_get("age_secs", |l: &mut TokenLaunch| (Utc::now() - l.launch_time).num_seconds())
+            .register_get("initial_price", |l: &mut TokenLaunch| l.initial_price)
+            .register_get("price", |l: &mut TokenLaunch| l.price)
+            .register_get("market_cap", |l: &mut TokenLaunch| l.market_cap)
+            .register_get("liquidity_sol", |l: &mut TokenLaunch| l.liquidity_sol)
+            .register_get("volume_24h", |l: &mut TokenLaunch| l.volume_24h)
+            .register_get("token_type", |l: &mut TokenLaunch| l.token_type.clone())
+            .register_get("has_flywheel", |l: &mut TokenLaunch| l.has_flywheel)
+            .register_get("flywheel_activity", |l: &mut TokenLaunch| l.flywheel_activity)
+            .register_get("creator_address", |l: &mut TokenLaunch| {
+                l.creator_address.clone().map(Dynamic::from).unwrap_or(Dynamic::UNIT)
+            })
+            .register_get("social_links", |l: &mut TokenLaunch| {
+                l.social_links.iter().cloned().map(Dynamic::from).collect::<rhai::Array>()
+            });
+        
+        engine.register_type_with_name::<MarketData>("MarketData")
+            .register_get("token_mint", |m: &mut MarketData| m.token_mint.clone())
+            .register_get("price", |m: &mut MarketData| m.price)
+            .register_get("volume_24h", |m: &mut MarketData| m.volume_24h)
+            .register_get("market_cap", |m: &mut MarketData| m.market_cap)
+            .register_get("price_change_1h", |m: &mut MarketData| m.price_change_1h)
+            .register_get("price_change_24h", |m: &mut MarketData| m.price_change_24h)
+            .register_get("price_change_7d", |m: &mut MarketData| m.price_change_7d)
+            .register_get("liquidity", |m: &mut MarketData| m.liquidity)
+            .register_get("holders", |m: &mut MarketData| m.holders as i64);
+        
+        engine.register_type_with_name::<Position>("Position")
+            .register_get("token_mint", |p: &mut Position| p.token_mint.clone())
+            .register_get("entry_price", |p: &mut Position| p.entry_price)
+            .register_get("current_price", |p: &mut Position| p.current_price)
+            .register_get("amount_sol", |p: &mut Position| p.amount_sol)
+            .register_get("token_amount", |p: &mut Position| p.token_amount)
+            .register_get("unrealized_pnl", |p: &mut Position| p.unrealized_pnl)
+            .register_get("high_water_price", |p: &mut Position| p.high_water_price)
+            .register_get("take_profits_hit", |p: &mut Position| p.take_profits_hit as i64)
+            .register_get("held_secs", |p: &mut Position| (Utc::now() - p.entry_time).num_seconds())
+            .register_get("strategy", |p: &mut Position| p.strategy.name().to_string())
+            .register_get("stop_loss", |p: &mut Position| {
+                p.stop_loss.map(Dynamic::from).unwrap_or(Dynamic::UNIT)
+            })
+            .register_get("take_profit", |p: &mut Position| {
+                p.take_profit.map(Dynamic::from).unwrap_or(Dynamic::UNIT)
+            });
+        
+        engine.register_type_with_name::<PoolState>("PoolState")
+            .register_get("liquidity", |p: &mut PoolState| p.liquidity)
+            .register_get("token_reserve", |p: &mut PoolState| p.token_a.liquidity_sol)
+            .register_get("sol_reserve", |p: &mut PoolState| p.token_b.liquidity_sol)
+            .register_get("fee_rate", |p: &mut PoolState| p.fee_rate)
+            .register_get("protocol_fee_rate", |p: &mut PoolState| p.protocol_fee_rate)
+            .register_get("creator_fee_rate", |p: &mut PoolState| p.creator_fee_rate)
+            .register_get("total_volume", |p: &mut PoolState| p.total_volume)
+            .register_get("total_fees", |p: &mut PoolState| p.total_fees)
+            .register_get("secs_since_swap", |p: &mut PoolState| (Utc::now() - p.last_swap_time).num_seconds());
+    }
+    
+    /// Reload changed scripts, at most once per `reload_interval_secs`.
+    pub async fn reload_if_changed(&self) -> Result<(), BotError> {
+        {
+            let mut last_reload = self.last_reload.write().await;
+            if let Some(last) = *last_reload {
+                if last.elapsed().as_secs() < self.config.reload_interval_secs {
+                    return Ok(());
+                }
+            }
+            *last_reload = Some(Instant::now());
+        }
+        
+        let dir = Path::new(&self.config.scripts_dir);
+        if !dir.is_dir() {
+            debug!("Scripts directory {} does not exist", self.config.scripts_dir);
+            self.scripts.write().await.clear();
+            return Ok(());
+        }
+        
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("rhai") {
+                let modified = std::fs::metadata(&path)?.modified()?;
+                found.push((path, modified));
+            }
+        }
+        
+        let mut scripts = self.scripts.write().await;
+        
+        // Forget scripts that were deleted
+        scripts.retain(|path, script| {
+            let keep = found.iter().any(|(p, _)| p == path);
+            if !keep {
+                info!("Unloaded script {}", script.name);
+            }
+            keep
+        });
+        
+        for (path, modified) in found {
+            if scripts.get(&path).map(|s| s.modified == modified).unwrap_or(false) {
+                continue;
+            }
+            
+            let name = path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("script")
+                .to_string();
+            
+            // On a compile error the previously loaded version stays active
+            match self.engine.compile_file(path.clone()) {
+                Ok(ast) => {
+                    info!("Loaded script {}", name);
+                    scripts.insert(path, LoadedScript { name, modified, ast });
+                }
+                Err(e) => {
+                    error!("Failed to compile script {}: {}", name, e);
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn evaluate_launch(&self, launch: &TokenLaunch, pool: Option<&PoolState>) -> ScriptDecision {
+        let pool = pool.cloned().map(Dynamic::from).unwrap_or(Dynamic::UNIT);
+        self.evaluate("on_launch", 2, |script| {
+            self.engine.call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &script.ast,
+                "on_launch",
+                (launch.clone(), pool.clone()),
+            )
+        }).await
+    }
+    
+    pub async fn evaluate_copy(
+        &self,
+        market: &MarketData,
+        trader_address: &str,
+        side: &str,
+        amount_sol: f64,
+    ) -> ScriptDecision {
+        self.evaluate("on_copy", 4, |script| {
+            self.engine.call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &script.ast,
+                "on_copy",
+                (market.clone(), trader_address.to_string(), side.to_string(), amount_sol),
+            )
+        }).await
+    }
+    
+    pub async fn evaluate_exit(&self, position: &Position, market: &MarketData) -> ScriptDecision {
+        self.evaluate("on_exit", 2, |script| {
+            self.engine.call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &script.ast,
+                "on_exit",
+                (position.clone(), market.clone()),
+            )
+        }).await
+    }
+    
+    async fn evaluate<F>(&self, hook: &str, arity: usize, call: F) -> ScriptDecision
+    where
+        F: Fn(&LoadedScript) -> Result<Dynamic, Box<rhai::EvalAltResult>>,
+    {
+        let scripts = self.scripts.read().await;
+        let mut decision = ScriptDecision::Abstain;
+        
+        for script in scripts.values() {
+            let has_hook = script.ast
+                .iter_functions()
+                .any(|f| f.name == hook && f.params.len() == arity);
+            if !has_hook {
+                continue;
+            }
+            
+            // A failing or over-budget script abstains rather than blocking trading
+            let result = match call(script) {
+                Ok(value) => ScriptDecision::from_dynamic(value),
+                Err(e) => Err(e.to_string()),
+            };
+            
+            match result {
+                Ok(result) => decision = decision.combine(result),
+                Err(e) => warn!("Script {} {} failed: {}", script.name, hook, e),
+            }
+        }
+        
+        decision
+    }
+    
+    pub async fn loaded_scripts(&self) -> Vec<String> {
+        self.scripts.read().await.values().map(|s| s.name.clone()).collect()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    config::{SizingConfig, SizingPolicy, TradingConfig},
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::metrics::MetricsSlice,
+    positions::PositionManager,
+    types::SniperStrategy,
+};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Turns a sizing policy, our bankroll and the pool's depth into an entry size.
+pub struct PositionSizer {
+    config: TradingConfig,
+    heaven_client: Arc<HeavenClient>,
+    positions: Arc<PositionManager>,
+}
+
+/// What is known about an entry before sizing it.
+#[derive(Debug, Clone)]
+pub struct SizingRequest<'a> {
+    pub token_mint: &'a str,
+    pub strategy: &'a SniperStrategy,
+    /// Per-strategy override of the configured policy
+    pub policy: Option<SizingPolicy>,
+    /// Strategy weighting applied before the caps
+    pub multiplier: f64,
+    /// Stop loss of the exit plan, as a fraction below entry
+    pub stop_loss: f64,
+    /// Hard cap from the caller's own limits
+    pub max_sol: f64,
+}
+
+impl PositionSizer {
+    pub fn new(
+        config: TradingConfig,
+        heaven_client: Arc<HeavenClient>,
+        positions: Arc<PositionManager>,
+    ) -> Self {
+        Self {
+            config,
+            heaven_client,
+            positions,
+        }
+    }
+    
+    /// SOL to spend on the entry; zero means the policy wants no position.
+    pub async fn size(&self, request: &SizingRequest<'_>) -> Result<f64, BotError> {
+        let equity = self.equity().await?;
+        if equity <= 0.0 {
+            return Ok(0.0);
+        }
+        
+        let policy = request.policy.unwrap_or(self.config.sizing.policy);
+        let fraction = match policy {
+            SizingPolicy::FixedFraction => self.config.risk_per_trade,
+            SizingPolicy::StopDistance => stop_distance_fraction(self.config.risk_per_trade, request.stop_loss),
+            SizingPolicy::Kelly => self.kelly_fraction(request.strategy).await,
+        };
+        
+        let liquidity_cap = self.liquidity_cap(request.token_mint).await?;
+        
+        let size = (equity * fraction * request.multiplier).min(request.max_sol).min(liquidity_cap).max(0.0);
+        debug!(
+            "Sized {} entry in {} at {:.4} SOL ({:?}, equity {:.4}, liquidity cap {:.4})",
+            request.strategy.name(), request.token_mint, size, policy, equity, liquidity_cap
+        );
+        
+        Ok(size)
+    }
+    
+    /// SOL balance plus the market value of open positions.
+    pub async fn equity(&self) -> Result<f64, BotError> {
+        Ok(self.heaven_client.get_sol_balance().await? + self.positions.market_value().await)
+    }
+    
+    /// Largest buy that stays within `max_price_impact` on the pool.
+    pub async fn liquidity_cap(&self, token_mint: &str) -> Result<f64, BotError> {
+        self.heaven_client.max_buy_for_impact(token_mint, self.config.sizing.max_price_impact).await
+    }
+    
+    // Falls back to the fixed fraction until the strategy has enough history
+    async fn kelly_fraction(&self, strategy: &SniperStrategy) -> f64 {
+        let slice = MetricsSlice::Strategy(strategy.name().to_string());
+        match self.positions.ledger().performance(&slice).await {
+            Some(metrics) if metrics.total_trades >= self.config.sizing.kelly_min_trades => {
+                kelly_fraction(&self.config.sizing, metrics.win_rate, metrics.average_win, metrics.average_loss)
+            }
+            _ => self.config.risk_per_trade,
+        }
+    }
+}
+
+/// Share of equity whose loss at the stop equals `risk_per_trade`.
+pub fn stop_distance_fraction(risk_per_trade: f64, stop_loss: f64) -> f64 {
+    if stop_loss <= 0.0 {
+        return risk_per_trade;
+    }
+    (risk_per_trade / stop_loss).min(1.0)
+}
+
+/// Scaled Kelly bet `W - (1 - W) / R`, where R is average win over average loss,
+/// floored at `kelly_min_fraction` so a losing streak can't stop sizing for good.
+pub fn kelly_fraction(sizing: &SizingConfig, win_rate: f64, average_win: f64, average_loss: f64) -> f64 {
+    let average_loss = average_loss.abs();
+    let full_kelly = if average_win <= 0.0 {
+        0.0
+    } else if average_loss <= 0.0 {
+        // No losses yet, so the payoff ratio is unbounded
+        win_rate
+    } else {
+        win_rate - (1.0 - win_rate) / (average_win / average_loss)
+    };
+    
+    (full_kelly * sizing.kelly_multiplier).clamp(sizing.kelly_min_fraction, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn sizing() -> SizingConfig {
+        SizingConfig {
+            kelly_multiplier: 0.5,
+            kelly_min_fraction: 0.005,
+            ..SizingConfig::default()
+        }
+    }
+    
+    #[test]
+    fn kelly_scales_the_full_bet() {
+        // 60% wins at 2:1 payoff is a 40% full Kelly bet
+        let fraction = kelly_fraction(&sizing(), 0.6, 2.0, 1.0);
+        assert!((fraction - 0.2).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn negative_edge_falls_back_to_the_minimum() {
+        assert_eq!(kelly_fraction(&sizing(), 0.2, 1.0, 1.0), 0.005);
+    }
+    
+    #[test]
+    fn no_wins_falls_back_to_the_minimum() {
+        assert_eq!(kelly_fraction(&sizing(), 0.0, 0.0, 1.0), 0.005);
+    }
+    
+    #[test]
+    fn no_losses_bets_the_win_rate() {
+        let fraction = kelly_fraction(&sizing(), 1.0, 1.5, 0.0);
+        assert!((fraction - 0.5).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn kelly_is_capped_at_all_in() {
+        let sizing = SizingConfig { kelly_multiplier: 1.0, ..sizing() };
+        assert_eq!(kelly_fraction(&sizing, 1.0, 3.0, 0.0), 1.0);
+    }
+    
+    #[test]
+    fn stop_distance_risks_the_configured_share() {
+        assert!((stop_distance_fraction(0.02, 0.1) - 0.2).abs() < 1e-9);
+        assert_eq!(stop_distance_fraction(0.02, 0.0), 0.02);
+        assert_eq!(stop_distance_fraction(0.5, 0.1), 1.0);
+    }
+}
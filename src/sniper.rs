@@ -1,10 +1,19 @@
 use crate::{
     config::BotConfig,
+    creators::CreatorTracker,
     error::BotError,
     heaven_client::HeavenClient,
     database::Database,
     monitoring::Metrics,
-    types::{TokenLaunch, Trade, SniperStrategy},
+    exits::{ExitPlan, ExitReason, ExitSignal, ExitSlice, ExitSlicer},
+    fade::FadeFeed,
+    launch_queue::LaunchQueue,
+    positions::{PositionManager, new_position},
+    risk::RiskEngine,
+    sizing::{PositionSizer, SizingRequest},
+    strategy_registry::StrategyRegistry,
+    scripting::{ScriptEngine, ScriptDecision},
+    types::{TokenLaunch, Trade, SniperStrategy, SnipePriority, SnipeTarget, SnipeCondition, MarketData, Position},
 };
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -18,7 +27,13 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error, debug};
 use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+
+// Idle snipe workers wake this often to notice shutdown
+const WORKER_IDLE_MS: u64 = 500;
+
+// Watch targets rejected by risk or sizing are not evaluated again for this long
+const TARGET_BACKOFF_SECS: i64 = 30;
 
 pub struct SniperBot {
     config: BotConfig,
@@ -26,15 +41,38 @@ pub struct SniperBot {
     heaven_client: Arc<HeavenClient>,
     database: Arc<Database>,
     metrics: Arc<Metrics>,
+    positions: Arc<PositionManager>,
+    risk: Arc<RiskEngine>,
+    sizer: Arc<PositionSizer>,
+    fade: Arc<FadeFeed>,
+    creators: Arc<CreatorTracker>,
     wallet: Arc<Keypair>,
     is_running: Arc<RwLock<bool>>,
     active_snipes: Arc<RwLock<HashMap<String, ActiveSnipe>>>,
-    strategies: Vec<SniperStrategy>,
+    strategies: StrategyRegistry,
+    watch_targets: Arc<RwLock<Vec<SnipeTarget>>>,
+    // Watch targets held back after a rejection, until the time given
+    target_backoff: RwLock<HashMap<String, DateTime<Utc>>>,
+    script_engine: Option<Arc<ScriptEngine>>,
     last_scan_time: Arc<RwLock<DateTime<Utc>>>,
+    exit_slicer: RwLock<ExitSlicer>,
+    launch_queue: LaunchQueue,
+    // Launches already queued or skipped, by launch time
+    seen_launches: RwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+// The first enabled strategy a launch matched, with the highest priority and
+// number of all strategies it matched
+#[derive(Debug, Clone)]
+struct LaunchMatch {
+    strategy: SniperStrategy,
+    priority: SnipePriority,
+    strength: usize,
 }
 
 #[derive(Debug, Clone)]
 struct ActiveSnipe {
+    position_id: String,
     token_mint: String,
     strategy: SniperStrategy,
     entry_price: f64,
@@ -43,6 +81,20 @@ struct ActiveSnipe {
     status: SnipeStatus,
 }
 
+impl ActiveSnipe {
+    fn from_position(position: &Position) -> Self {
+        Self {
+            position_id: position.id.clone(),
+            token_mint: position.token_mint.clone(),
+            strategy: position.strategy.clone(),
+            entry_price: position.entry_price,
+            entry_time: position.entry_time,
+            trade_amount: position.amount_sol,
+            status: SnipeStatus::Executed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum SnipeStatus {
     Pending,
@@ -58,10 +110,32 @@ impl SniperBot {
         heaven_client: Arc<HeavenClient>,
         database: Arc<Database>,
         metrics: Arc<Metrics>,
+        positions: Arc<PositionManager>,
+        risk: Arc<RiskEngine>,
+        sizer: Arc<PositionSizer>,
+        fade: Arc<FadeFeed>,
         wallet: Arc<Keypair>,
     ) -> Result<Self, BotError> {
         // Initialize sniper strategies
-        let strategies = Self::initialize_strategies(&config)?;
+        let strategies = StrategyRegistry::from_config(&config.sniper)?;
+        
+        // Initialize rule scripts
+        let script_engine = if config.scripting.enabled {
+            Some(Arc::new(ScriptEngine::new(&config.scripting)?))
+        } else {
+            None
+        };
+        
+        let exit_slicer = RwLock::new(ExitSlicer::new(config.trading.execution.twap_interval_secs));
+        
+        let launch_queue = LaunchQueue::new(config.sniper.queue.clone());
+        
+        let creators = Arc::new(CreatorTracker::new(
+            config.sniper.creators.clone(),
+            config.sniper.verified_creators.clone(),
+            heaven_client.clone(),
+            database.clone(),
+        ));
         
         Ok(Self {
             config,
@@ -69,11 +143,22 @@ impl SniperBot {
             heaven_client,
             database,
             metrics,
+            positions,
+            risk,
+            sizer,
+            fade,
+            creators,
             wallet,
             is_running: Arc::new(RwLock::false()),
             active_snipes: Arc::new(RwLock::new(HashMap::new())),
             strategies,
+            watch_targets: Arc::new(RwLock::new(Vec::new())),
+            target_backoff: RwLock::new(HashMap::new()),
+            script_engine,
             last_scan_time: Arc<RwLock::new(Utc::now()),
+            exit_slicer,
+            launch_queue,
+            seen_launches: RwLock::new(HashMap::new()),
         })
     }
     
@@ -81,8 +166,28 @@ impl SniperBot {
         info!("Starting Sniper Bot...");
         *self.is_running.write().await = true;
         
-        // Start the main sniper loop
-        self.main_sniper_loop().await?;
+        // Resume snipes that were open before a restart
+        self.restore_active_snipes().await;
+        
+        // Load pre-staged watch targets
+        self.load_watch_targets().await?;
+        
+        // Follow creators' launches to their outcome in the background
+        if self.creators.enabled() {
+            self.creators.load().await?;
+            let creators = self.creators.clone();
+            let is_running = self.is_running.clone();
+            tokio::spawn(async move {
+                if let Err(e) = creators.run(is_running).await {
+                    error!("Creator tracking error: {}", e);
+                }
+            });
+        }
+        
+        // Start the main sniper loop alongside the snipe workers
+        let workers = (0..self.config.sniper.queue.workers).map(|_| self.snipe_worker());
+        let (result, _) = tokio::join!(self.main_sniper_loop(), futures::future::join_all(workers));
+        result?;
         
         Ok(())
     }
@@ -101,12 +206,24 @@ impl SniperBot {
         while *self.is_running.read().await {
             interval.tick().await;
             
+            // Pick up edited rule scripts
+            if let Some(engine) = &self.script_engine {
+                if let Err(e) = engine.reload_if_changed().await {
+                    warn!("Failed to reload scripts: {}", e);
+                }
+            }
+            
             // Scan for new launches
             if let Err(e) = self.scan_new_launches().await {
                 warn!("Failed to scan for new launches: {}", e);
                 continue;
             }
             
+            // Evaluate watchlist targets against live market data
+            if let Err(e) = self.process_watch_targets().await {
+                warn!("Failed to process watch targets: {}", e);
+            }
+            
             // Process active snipes
             if let Err(e) = self.process_active_snipes().await {
                 warn!("Failed to process active snipes: {}", e);
@@ -119,41 +236,120 @@ impl SniperBot {
         Ok(())
     }
     
+    /// Queue launches not seen before that match a strategy. Launches are
+    /// recognised by mint rather than by scan time, so a launch landing while a
+    /// scan runs is picked up by the next one.
     async fn scan_new_launches(&self) -> Result<(), BotError> {
         let new_launches = self.heaven_client.scan_new_launches().await?;
-        let mut last_scan = self.last_scan_time.write().await;
+        let now = Utc::now();
+        let max_age = Duration::milliseconds(self.config.sniper.queue.max_launch_age_ms as i64);
         
         for launch in new_launches {
-            // Check if this is a new launch since our last scan
-            if launch.launch_time > *last_scan {
-                debug!("New launch detected: {}", launch.token_mint);
+            if now - launch.launch_time > max_age {
+                continue;
+            }
+            
+            {
+                let mut seen = self.seen_launches.write().await;
+                if seen.contains_key(&launch.token_mint) {
+                    continue;
+                }
+                seen.insert(launch.token_mint.clone(), launch.launch_time);
+            }
+            debug!("New launch detected: {}", launch.token_mint);
+            
+            self.creators.watch_launch(&launch);
+            
+            // Evaluate launch against our strategies
+            if let Some(matched) = self.evaluate_launch(&launch).await {
+                info!(
+                    "Launch {} matches strategy {:?} ({:?} priority, {} matches)",
+                    launch.token_mint, matched.strategy, matched.priority, matched.strength
+                );
                 
-                // Evaluate launch against our strategies
-                if let Some(strategy) = self.evaluate_launch(&launch).await {
-                    info!("Launch {} matches strategy: {:?}", launch.token_mint, strategy);
-                    
-                    // Execute snipe
-                    if let Err(e) = self.execute_snipe(&launch, &strategy).await {
-                        error!("Failed to execute snipe for {}: {}", launch.token_mint, e);
-                    }
+                if let Some(evicted) = self.launch_queue.push(launch, matched.strategy, matched.priority, matched.strength) {
+                    warn!("Launch queue full, dropped {}", evicted.launch.token_mint);
+                    self.metrics.increment_counter("launch_queue_evicted", 1).await;
                 }
             }
         }
         
-        *last_scan = Utc::now();
+        // Seen launches only need remembering until they are too old to snipe
+        self.seen_launches.write().await.retain(|_, launch_time| now - *launch_time <= max_age);
+        self.metrics.set_gauge("launch_queue_depth", self.launch_queue.len() as f64).await;
+        
+        *self.last_scan_time.write().await = now;
         Ok(())
     }
     
-    async fn evaluate_launch(&self, launch: &TokenLaunch) -> Option<SniperStrategy> {
-        for strategy in &self.strategies {
-            if self.matches_strategy(launch, strategy).await {
-                return Some(strategy.clone());
+    /// Take launches off the queue, highest priority first, and snipe them.
+    async fn snipe_worker(&self) {
+        let idle = std::time::Duration::from_millis(WORKER_IDLE_MS);
+        
+        while *self.is_running.read().await {
+            let Ok(queued) = tokio::time::timeout(idle, self.launch_queue.next()).await else {
+                continue;
+            };
+            
+            let now = Utc::now();
+            self.metrics.set_gauge("launch_queue_depth", self.launch_queue.len() as f64).await;
+            self.metrics.record_histogram(
+                "launch_queue_wait_ms",
+                (now - queued.queued_at).num_milliseconds() as f64,
+            ).await;
+            
+            if queued.is_stale(now, self.config.sniper.queue.max_launch_age_ms) {
+                debug!("Dropping stale launch {}", queued.launch.token_mint);
+                self.metrics.increment_counter("launch_queue_stale", 1).await;
+                continue;
+            }
+            
+            if let Err(e) = self.execute_snipe(&queued.launch, &queued.strategy).await {
+                error!("Failed to execute snipe for {}: {}", queued.launch.token_mint, e);
+            }
+        }
+    }
+    
+    /// The first matching strategy, raised to the highest priority among all
+    /// strategies the launch matches. Launch-level checks and rule scripts run
+    /// once per launch, not once per strategy.
+    async fn evaluate_launch(&self, launch: &TokenLaunch) -> Option<LaunchMatch> {
+        if !self.launch_allowed(launch).await {
+            return None;
+        }
+        
+        let mut matched: Option<LaunchMatch> = None;
+        
+        for strategy in self.strategies.enabled() {
+            // Strategy-specific filters
+            if !strategy.matches(launch) {
+                continue;
+            }
+            
+            let priority = strategy.definition.priority;
+            match &mut matched {
+                Some(matched) => {
+                    matched.priority = matched.priority.max(priority);
+                    matched.strength += 1;
+                }
+                None => {
+                    matched = Some(LaunchMatch {
+                        strategy: strategy.strategy.clone(),
+                        priority,
+                        strength: 1,
+                    });
+                }
             }
         }
-        None
+        
+        // Rule scripts can veto a match
+        match matched {
+            Some(matched) if self.scripts_allow_launch(launch).await => Some(matched),
+            _ => None,
+        }
     }
     
-    async fn matches_strategy(&self, launch: &TokenLaunch, strategy: &SniperStrategy) -> bool {
+    async fn launch_allowed(&self, launch: &TokenLaunch) -> bool {
         // Check blacklist/whitelist
         if !self.config.sniper.whitelisted_tokens.is_empty() {
             if !self.config.sniper.whitelisted_tokens.contains(&launch.token_mint) {
@@ -165,6 +361,18 @@ impl SniperBot {
             return false;
         }
         
+        // Stay out of what faded wallets buy or create
+        if self.fade.avoids_launch(launch).await {
+            debug!("Skipping {}: faded", launch.token_mint);
+            return false;
+        }
+        
+        // Skip blacklisted and poorly rated creators
+        if !self.creators.allows(launch).await {
+            debug!("Skipping {}: creator reputation", launch.token_mint);
+            return false;
+        }
+        
         // Check market cap requirements
         if launch.market_cap < self.config.sniper.min_market_cap {
             return false;
@@ -180,27 +388,21 @@ impl SniperBot {
         }
         
         // Check volume threshold
-        if launch.volume_24h < self.config.sniper.volume_threshold {
-            return false;
-        }
+        launch.volume_24h >= self.config.sniper.volume_threshold
+    }
+    
+    async fn scripts_allow_launch(&self, launch: &TokenLaunch) -> bool {
+        let Some(engine) = &self.script_engine else {
+            return true;
+        };
         
-        // Strategy-specific checks
-        match strategy {
-            SniperStrategy::CreatorToken => {
-                launch.token_type == "creator" && launch.has_flywheel
-            }
-            SniperStrategy::CommunityToken => {
-                launch.token_type == "community"
-            }
-            SniperStrategy::HighVolume => {
-                launch.volume_24h > self.config.sniper.volume_threshold * 10.0
-            }
-            SniperStrategy::LowMarketCap => {
-                launch.market_cap < 10000.0 // $10k threshold
-            }
-            SniperStrategy::FlywheelActive => {
-                launch.has_flywheel && launch.flywheel_activity > 0.0
+        let pool_state = self.heaven_client.get_pool_state(&launch.token_mint).await.ok();
+        match engine.evaluate_launch(launch, pool_state.as_ref()).await {
+            ScriptDecision::Reject => {
+                debug!("Launch {} rejected by rule scripts", launch.token_mint);
+                false
             }
+            _ => true,
         }
     }
     
@@ -208,7 +410,23 @@ impl SniperBot {
         info!("Executing snipe for {} with strategy {:?}", launch.token_mint, strategy);
         
         // Calculate trade amount based on strategy and risk
-        let trade_amount = self.calculate_trade_amount(strategy, launch).await?;
+        let trade_amount = self.calculate_trade_amount(strategy, &launch.token_mint, self.config.sniper.max_sol_per_trade).await?;
+        
+        self.open_snipe(&launch.token_mint, launch.price, trade_amount, strategy).await?;
+        Ok(())
+    }
+    
+    async fn open_snipe(
+        &self,
+        token_mint: &str,
+        price: f64,
+        trade_amount: f64,
+        strategy: &SniperStrategy,
+    ) -> Result<bool, BotError> {
+        if trade_amount <= 0.0 {
+            debug!("Sizing left nothing to buy in {}", token_mint);
+            return Ok(false);
+        }
         
         // Check if we have sufficient balance
         let balance = self.heaven_client.get_sol_balance().await?;
@@ -218,25 +436,61 @@ impl SniperBot {
             ));
         }
         
-        // Create and execute the trade
-        let trade = self.create_snipe_trade(launch, trade_amount, strategy).await?;
+        // Held until the position is open so concurrent snipes see this buy
+        let _permit = match self.risk.check_buy("sniper", token_mint, trade_amount).await {
+            Ok(permit) => permit,
+            Err(_) => return Ok(false),
+        };
         
-        // Execute the trade
-        let result = self.execute_trade(&trade).await?;
+        // Create and execute the trade
+        let mut trade = self.create_snipe_trade(token_mint, trade_amount, strategy).await?;
+        
+        // Execute the trade; errors count towards the failure breaker too
+        let result = match self.execute_trade(&trade).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.risk.record_trade_result(false).await;
+                return Err(e);
+            }
+        };
+        self.risk.record_trade_result(result.success).await;
         
         if result.success {
+            // Entry comes from what we actually paid, not the launch or quote price
+            let signature = result.signature.as_deref()
+                .ok_or_else(|| BotError::Transaction(format!("Snipe of {} landed without a signature", token_mint)))?;
+            if let Err(e) = self.heaven_client.settle_trade(&mut trade, signature).await {
+                self.database.record_trade(&trade).await?;
+                return Err(e);
+            }
+            if trade.price > 0.0 && price > 0.0 {
+                debug!("Snipe entry for {} filled at {:.8} vs {:.8} expected", token_mint, trade.price, price);
+            }
+            
+            // Persist the position before tracking it in memory; fees are part of the cost basis
+            self.positions.open_position(new_position(
+                trade.id.clone(),
+                token_mint,
+                "sniper",
+                strategy.clone(),
+                trade.price,
+                trade.sol_spent,
+                trade.token_amount,
+            ), trade.fee_sol).await?;
+            
             // Record successful snipe
             let active_snipe = ActiveSnipe {
-                token_mint: launch.token_mint.clone(),
+                position_id: trade.id.clone(),
+                token_mint: token_mint.to_string(),
                 strategy: strategy.clone(),
-                entry_price: launch.price,
+                entry_price: trade.price,
                 entry_time: Utc::now(),
-                trade_amount,
+                trade_amount: trade.sol_spent,
                 status: SnipeStatus::Executed,
             };
             
             self.active_snipes.write().await.insert(
-                launch.token_mint.clone(),
+                trade.id.clone(),
                 active_snipe,
             );
             
@@ -244,49 +498,202 @@ impl SniperBot {
             self.database.record_trade(&trade).await?;
             
             // Update metrics
-            self.metrics.record_successful_snipe(trade_amount).await;
+            self.metrics.record_successful_snipe(trade.amount_sol).await;
             
-            info!("Successfully sniped {} for {:.4} SOL", launch.token_mint, trade_amount);
+            info!(
+                "Successfully sniped {} for {:.4} SOL ({:.6} SOL fees)",
+                token_mint, trade.amount_sol, trade.fee_sol
+            );
         } else {
-            error!("Snipe failed for {}: {}", launch.token_mint, result.error.unwrap_or_default());
+            error!("Snipe failed for {}: {}", token_mint, result.error.unwrap_or_default());
             self.metrics.record_failed_snipe(trade_amount).await;
         }
         
+        Ok(result.success)
+    }
+    
+    async fn restore_active_snipes(&self) {
+        let positions = self.positions.open_positions_by_source("sniper").await;
+        let mut active_snipes = self.active_snipes.write().await;
+        
+        // Keyed by position, as a token can be sniped more than once
+        for position in &positions {
+            let (stop_loss, take_profit) = self.exit_plan(&position.strategy).levels(position);
+            if let Err(e) = self.positions.set_exit_levels(&position.id, stop_loss, take_profit).await {
+                warn!("Failed to restore exit levels of {}: {}", position.id, e);
+            }
+            active_snipes.insert(position.id.clone(), ActiveSnipe::from_position(position));
+        }
+        
+        if !positions.is_empty() {
+            info!("Restored {} open snipes", positions.len());
+        }
+    }
+    
+    async fn load_watch_targets(&self) -> Result<(), BotError> {
+        // Config targets that already fired stay off after a restart
+        let fired = self.database.get_inactive_snipe_target_mints().await?;
+        let mut targets: Vec<SnipeTarget> = self.config.sniper.watchlist.iter()
+            .filter(|t| !fired.contains(&t.token_mint))
+            .cloned()
+            .collect();
+        
+        // Database targets override config entries for the same mint
+        for target in self.database.get_active_snipe_targets().await? {
+            targets.retain(|t| t.token_mint != target.token_mint);
+            targets.push(target);
+        }
+        
+        // Highest priority first
+        targets.sort_by(|a, b| b.priority.cmp(&a.priority));
+        
+        info!("Loaded {} snipe watch targets", targets.len());
+        *self.watch_targets.write().await = targets;
+        
         Ok(())
     }
     
-    async fn calculate_trade_amount(&self, strategy: &SniperStrategy, launch: &TokenLaunch) -> Result<f64, BotError> {
-        let base_amount = self.config.sniper.max_sol_per_trade;
-        
-        // Adjust based on strategy
-        let multiplier = match strategy {
-            SniperStrategy::CreatorToken => 1.0, // Full amount for creator tokens
-            SniperStrategy::CommunityToken => 0.7, // 70% for community tokens
-            SniperStrategy::HighVolume => 1.2, // 120% for high volume
-            SniperStrategy::LowMarketCap => 0.8, // 80% for low market cap
-            SniperStrategy::FlywheelActive => 1.1, // 110% for active flywheel
-        };
+    pub async fn add_watch_target(&self, target: SnipeTarget) -> Result<(), BotError> {
+        self.database.record_snipe_target(&target).await?;
+        
+        let mut targets = self.watch_targets.write().await;
+        targets.retain(|t| t.token_mint != target.token_mint);
+        targets.push(target);
+        targets.sort_by(|a, b| b.priority.cmp(&a.priority));
+        
+        Ok(())
+    }
+    
+    async fn process_watch_targets(&self) -> Result<(), BotError> {
+        // Snapshot so the lock is not held across RPC calls; already sorted by priority
+        let targets = self.watch_targets.read().await.clone();
+        
+        let now = Utc::now();
+        self.target_backoff.write().await.retain(|_, until| *until > now);
+        
+        for target in targets {
+            if self.active_snipes.read().await.values().any(|s| s.token_mint == target.token_mint) {
+                continue;
+            }
+            if self.target_backoff.read().await.contains_key(&target.token_mint) {
+                continue;
+            }
+            
+            let market_data = match self.heaven_client.get_market_data(&target.token_mint).await {
+                Ok(data) => data,
+                Err(e) => {
+                    debug!("No market data for watch target {}: {}", target.token_mint, e);
+                    continue;
+                }
+            };
+            
+            if !self.target_conditions_met(&target, &market_data).await {
+                continue;
+            }
+            
+            info!("Watch target {} triggered ({:?} priority)", target.token_mint, target.priority);
+            
+            let trade_amount = match self.calculate_trade_amount(&target.strategy, &target.token_mint, target.max_amount).await {
+                Ok(amount) => amount,
+                Err(e) => {
+                    warn!("Failed to size watch target {}: {}", target.token_mint, e);
+                    self.back_off_target(&target.token_mint).await;
+                    continue;
+                }
+            };
+            match self.open_snipe(&target.token_mint, market_data.price, trade_amount, &target.strategy).await {
+                Ok(true) => {
+                    // Targets are one-shot: drop it so a later exit does not trigger a re-entry
+                    self.watch_targets.write().await.retain(|t| t.token_mint != target.token_mint);
+                    self.database.deactivate_snipe_target(&target).await?;
+                }
+                Ok(false) => self.back_off_target(&target.token_mint).await,
+                Err(e) => {
+                    error!("Failed to execute watch target {}: {}", target.token_mint, e);
+                }
+            }
+        }
         
-        // Adjust based on risk
-        let risk_multiplier = if launch.market_cap < 1000.0 { 0.5 } else { 1.0 };
+        Ok(())
+    }
+    
+    async fn back_off_target(&self, token_mint: &str) {
+        let until = Utc::now() + Duration::seconds(TARGET_BACKOFF_SECS);
+        debug!("Backing off watch target {} until {}", token_mint, until);
+        self.target_backoff.write().await.insert(token_mint.to_string(), until);
+    }
+    
+    async fn target_conditions_met(&self, target: &SnipeTarget, market_data: &MarketData) -> bool {
+        if self.config.sniper.blacklisted_tokens.contains(&target.token_mint) {
+            return false;
+        }
         
-        let adjusted_amount = base_amount * multiplier * risk_multiplier;
+        // A non-zero target price acts as a limit price for the entry
+        if target.target_price > 0.0 && market_data.price > target.target_price {
+            return false;
+        }
         
-        // Ensure we don't exceed max amount
-        Ok(adjusted_amount.min(self.config.sniper.max_sol_per_trade))
+        for condition in &target.conditions {
+            if !self.condition_met(condition, &target.token_mint, market_data).await {
+                return false;
+            }
+        }
+        
+        true
+    }
+    
+    async fn condition_met(&self, condition: &SnipeCondition, token_mint: &str, market_data: &MarketData) -> bool {
+        match condition {
+            SnipeCondition::MarketCapBelow(max) => market_data.market_cap < *max,
+            SnipeCondition::VolumeAbove(min) => market_data.volume_24h > *min,
+            SnipeCondition::PriceBelow(max) => market_data.price < *max,
+            SnipeCondition::LiquidityAbove(min) => market_data.liquidity > *min,
+            SnipeCondition::HasFlywheel => {
+                matches!(
+                    self.heaven_client.get_flywheel_info(token_mint).await,
+                    Ok(Some(flywheel)) if flywheel.is_active
+                )
+            }
+            SnipeCondition::CreatorVerified => {
+                match self.database.get_token_launch(token_mint).await {
+                    Ok(Some(launch)) => match &launch.creator_address {
+                        Some(creator) => self.creators.is_verified(creator).await,
+                        None => false,
+                    },
+                    _ => false,
+                }
+            }
+            SnipeCondition::Custom(name) => {
+                warn!("Unsupported custom snipe condition '{}' on {}", name, token_mint);
+                false
+            }
+        }
+    }
+    
+    async fn calculate_trade_amount(&self, strategy: &SniperStrategy, token_mint: &str, max_sol: f64) -> Result<f64, BotError> {
+        let registered = self.strategies.get(strategy);
+        
+        self.sizer.size(&SizingRequest {
+            token_mint,
+            strategy,
+            policy: registered.and_then(|s| s.definition.sizing),
+            multiplier: registered.map(|s| s.definition.size_multiplier).unwrap_or(1.0),
+            stop_loss: self.exit_plan(strategy).stop_loss(),
+            max_sol: max_sol.min(self.config.sniper.max_sol_per_trade),
+        }).await
     }
     
-    async fn create_snipe_trade(&self, launch: &TokenLaunch, amount: f64, strategy: &SniperStrategy) -> Result<Trade, BotError> {
+    async fn create_snipe_trade(&self, token_mint: &str, amount: f64, strategy: &SniperStrategy) -> Result<Trade, BotError> {
         // Get quote from Heaven AMM
         let quote = self.heaven_client.get_buy_quote(
-            &launch.token_mint,
+            token_mint,
             amount,
-            self.config.sniper.max_slippage,
+            self.strategy_slippage(strategy),
         ).await?;
         
         // Create trade instruction
         let trade_ix = self.heaven_client.create_buy_instruction(
-            &launch.token_mint,
+            token_mint,
             amount,
             quote.token_amount,
             &self.wallet.pubkey(),
@@ -303,7 +710,7 @@ impl SniperBot {
         // Create trade object
         Ok(Trade {
             id: uuid::Uuid::new_v4().to_string(),
-            token_mint: launch.token_mint.clone(),
+            token_mint: token_mint.to_string(),
             trade_type: "buy".to_string(),
             amount_sol: amount,
             token_amount: quote.token_amount,
@@ -313,6 +720,11 @@ impl SniperBot {
             timestamp: Utc::now(),
             status: "pending".to_string(),
             transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: None,
         })
     }
     
@@ -344,82 +756,193 @@ impl SniperBot {
     }
     
     async fn process_active_snipes(&self) -> Result<(), BotError> {
-        let mut active_snipes = self.active_snipes.write().await;
+        // Sells wait on RPCs, so they run on a snapshot and the workers can
+        // keep recording new snipes meanwhile
+        let snipes: Vec<ActiveSnipe> = self.active_snipes.read().await.values().cloned().collect();
         let mut to_remove = Vec::new();
         
-        for (token_mint, snipe) in active_snipes.iter_mut() {
+        for snipe in &snipes {
             match snipe.status {
                 SnipeStatus::Executed => {
-                    // Check if we should sell
-                    if self.should_sell_snipe(snipe).await {
-                        if let Err(e) = self.sell_snipe(snipe).await {
-                            warn!("Failed to sell snipe {}: {}", token_mint, e);
-                        } else {
-                            snipe.status = SnipeStatus::Sold;
-                            to_remove.push(token_mint.clone());
+                    // Check if we should sell, or sell the next slice of a split exit
+                    let signal = self.exit_signal(snipe).await;
+                    if let Some(slice) = self.next_exit_slice(snipe, signal.as_ref()).await {
+                        match self.sell_snipe(snipe, &slice).await {
+                            Ok(true) => to_remove.push(snipe.position_id.clone()),
+                            Ok(false) => {}
+                            Err(e) => warn!("Failed to sell snipe {}: {}", snipe.token_mint, e),
                         }
                     }
                 }
                 SnipeStatus::Failed | SnipeStatus::Sold => {
-                    to_remove.push(token_mint.clone());
+                    to_remove.push(snipe.position_id.clone());
                 }
                 _ => {}
             }
         }
         
         // Remove completed snipes
-        for token_mint in to_remove {
-            active_snipes.remove(&token_mint);
+        if !to_remove.is_empty() {
+            let mut active_snipes = self.active_snipes.write().await;
+            for position_id in to_remove {
+                active_snipes.remove(&position_id);
+            }
         }
         
         Ok(())
     }
     
-    async fn should_sell_snipe(&self, snipe: &ActiveSnipe) -> bool {
-        // Get current price
-        if let Ok(current_price) = self.heaven_client.get_token_price(&snipe.token_mint).await {
-            let price_change = (current_price - snipe.entry_price) / snipe.entry_price;
-            
-            // Sell if profit target reached or stop loss hit
-            price_change >= self.config.trading.profit_taking_percentage ||
-            price_change <= -self.config.trading.stop_loss_percentage
-        } else {
-            false
+    async fn exit_signal(&self, snipe: &ActiveSnipe) -> Option<ExitSignal> {
+        if self.risk.is_flattening().await {
+            return Some(ExitSignal::full(ExitReason::KillSwitch));
         }
+        
+        if self.fade.sell_due(&snipe.token_mint).await {
+            return Some(ExitSignal::full(ExitReason::Faded));
+        }
+        
+        let market_data = match self.heaven_client.get_market_data(&snipe.token_mint).await {
+            Ok(market_data) => market_data,
+            Err(e) => {
+                debug!("No market data for {}: {}", snipe.token_mint, e);
+                return None;
+            }
+        };
+        
+        let position = match self.positions.update_price(&snipe.position_id, market_data.price).await {
+            Ok(Some(position)) => position,
+            Ok(None) => return None,
+            Err(e) => {
+                warn!("Failed to update position for {}: {}", snipe.token_mint, e);
+                return None;
+            }
+        };
+        
+        // Script exit rules
+        if let Some(engine) = &self.script_engine {
+            if engine.evaluate_exit(&position, &market_data).await == ScriptDecision::Allow {
+                info!("Exit for {} triggered by rule scripts", snipe.token_mint);
+                return Some(ExitSignal::full(ExitReason::Script));
+            }
+        }
+        
+        self.exit_plan(&snipe.strategy).evaluate(&position)
+    }
+    
+    async fn next_exit_slice(&self, snipe: &ActiveSnipe, signal: Option<&ExitSignal>) -> Option<ExitSlice> {
+        if signal.is_none() && !self.exit_slicer.read().await.is_pending(&snipe.position_id) {
+            return None;
+        }
+        
+        let position = self.positions.get_position(&snipe.position_id).await?;
+        let max_exit_impact = self.config.trading.execution.max_exit_impact;
+        let max_tokens = match self.heaven_client.max_sell_for_impact(&snipe.token_mint, max_exit_impact).await {
+            Ok(max_tokens) => max_tokens,
+            Err(e) => {
+                warn!("No pool depth for exit from {}: {}", snipe.token_mint, e);
+                return None;
+            }
+        };
+        
+        self.exit_slicer.write().await.next_slice(&position, signal, max_tokens)
+    }
+    
+    fn exit_plan(&self, strategy: &SniperStrategy) -> ExitPlan {
+        let rules = self.strategies.get(strategy)
+            .map(|s| s.definition.exit.clone())
+            .unwrap_or_default();
+        ExitPlan::new(&rules, &self.config.trading)
     }
     
-    async fn sell_snipe(&self, snipe: &ActiveSnipe) -> Result<(), BotError> {
-        info!("Selling snipe for {} at {:.4} SOL", snipe.token_mint, snipe.trade_amount);
+    fn strategy_slippage(&self, strategy: &SniperStrategy) -> f64 {
+        self.strategies.get(strategy)
+            .and_then(|s| s.definition.max_slippage)
+            .unwrap_or(self.config.sniper.max_slippage)
+    }
+    
+    /// Sell one exit slice of a snipe. Returns whether the position is now closed.
+    async fn sell_snipe(&self, snipe: &ActiveSnipe, slice: &ExitSlice) -> Result<bool, BotError> {
+        let Some(position) = self.positions.get_position(&snipe.position_id).await else {
+            self.exit_slicer.write().await.forget(&snipe.position_id);
+            return Ok(true);
+        };
         
-        // Get current token balance
+        // Never sell more than the wallet actually holds
         let token_balance = self.heaven_client.get_token_balance(&snipe.token_mint).await?;
+        let token_amount = slice.tokens.min(token_balance);
+        
+        info!(
+            "Selling {:.0}% of snipe for {} ({:?})",
+            token_amount / position.token_amount * 100.0, snipe.token_mint, slice.reason
+        );
+        
+        // Quoted amounts are replaced by the real fill once confirmed
+        let slippage = self.strategy_slippage(&snipe.strategy);
+        let quote = self.heaven_client.get_sell_quote(&snipe.token_mint, token_amount, slippage).await?;
         
         // Create sell trade
-        let sell_trade = Trade {
+        let mut sell_trade = Trade {
             id: uuid::Uuid::new_v4().to_string(),
             token_mint: snipe.token_mint.clone(),
             trade_type: "sell".to_string(),
-            amount_sol: 0.0, // Will be calculated
-            token_amount: token_balance,
-            price: 0.0, // Will be calculated
-            slippage: self.config.sniper.max_slippage,
+            amount_sol: quote.sol_amount,
+            token_amount,
+            price: quote.price,
+            slippage,
             strategy: snipe.strategy.clone(),
             timestamp: Utc::now(),
             status: "pending".to_string(),
             transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: None,
         };
         
         // Execute sell
         let result = self.execute_trade(&sell_trade).await?;
         
-        if result.success {
+        if !result.success {
+            error!("Failed to sell snipe for {}: {}", snipe.token_mint, result.error.unwrap_or_default());
+            return Ok(false);
+        }
+        
+        // Proceeds are booked only from a readable fill; the position stays open otherwise
+        let signature = result.signature.as_deref()
+            .ok_or_else(|| BotError::Transaction(format!("Sell of {} landed without a signature", snipe.token_mint)))?;
+        let settled = self.heaven_client.settle_trade(&mut sell_trade, signature).await;
+        self.database.record_trade(&sell_trade).await?;
+        settled?;
+        
+        // A sell capped by the wallet balance still closes the position
+        let tokens_sold = if slice.closes { position.token_amount } else { sell_trade.token_amount };
+        let updated = self.positions.reduce_position(
+            &snipe.position_id,
+            tokens_sold,
+            sell_trade.sol_received,
+            sell_trade.fee_sol,
+        ).await?;
+        
+        if let ExitReason::TakeProfit(level) = slice.reason {
+            self.positions.record_take_profit(&snipe.position_id, level).await?;
+        }
+        
+        let closed = updated.map(|p| p.status == "closed").unwrap_or(true);
+        let mut exit_slicer = self.exit_slicer.write().await;
+        if closed {
+            exit_slicer.forget(&snipe.position_id);
+        } else {
+            exit_slicer.record_sold(&snipe.position_id, tokens_sold);
+        }
+        drop(exit_slicer);
+        
+        if closed {
             info!("Successfully sold snipe for {}", snipe.token_mint);
             self.metrics.record_snipe_sale(snipe.trade_amount).await;
-        } else {
-            error!("Failed to sell snipe for {}: {}", snipe.token_mint, result.error.unwrap_or_default());
         }
         
-        Ok(())
+        Ok(closed)
     }
     
     async fn update_sniper_metrics(&self) {
@@ -427,19 +950,6 @@ impl SniperBot {
         self.metrics.update_active_snipes(active_count).await;
     }
     
-    fn initialize_strategies(config: &BotConfig) -> Result<Vec<SniperStrategy>, BotError> {
-        let mut strategies = Vec::new();
-        
-        // Add default strategies based on config
-        strategies.push(SniperStrategy::CreatorToken);
-        strategies.push(SniperStrategy::CommunityToken);
-        strategies.push(SniperStrategy::HighVolume);
-        strategies.push(SniperStrategy::LowMarketCap);
-        strategies.push(SniperStrategy::FlywheelActive);
-        
-        Ok(strategies)
-    }
-    
     pub async fn get_sniper_status(&self) -> SniperStatus {
         let active_snipes = self.active_snipes.read().await;
         
@@ -447,6 +957,8 @@ impl SniperBot {
             is_running: *self.is_running.read().await,
             active_snipes: active_snipes.len(),
             total_strategies: self.strategies.len(),
+            watch_targets: self.watch_targets.read().await.len(),
+            queued_launches: self.launch_queue.len(),
             last_scan: *self.last_scan_time.read().await,
         }
     }
@@ -464,5 +976,7 @@ pub struct SniperStatus {
     pub is_running: bool,
     pub active_snipes: usize,
     pub total_strategies: usize,
+    pub watch_targets: usize,
+    pub queued_launches: usize,
     pub last_scan: DateTime<Utc>,
 }
@@ -0,0 +1,569 @@
+use crate::{
+    config::BotConfig,
+    error::BotError,
+    heaven_client::HeavenClient,
+    database::Database,
+    monitoring::Metrics,
+    positions::{PositionManager, new_position},
+    risk::RiskEngine,
+    types::{TokenLaunch, Trade, Trader, SniperStrategy},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    instruction::Instruction,
+    compute_budget::ComputeBudgetInstruction,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tracing::{info, warn, error, debug};
+use chrono::{DateTime, Duration, Utc};
+
+// Trader trades this far behind the watermark are still deduplicated one by one
+const SEEN_TRADE_WINDOW_SECS: i64 = 6 * 3600;
+
+/// A user-supplied trading strategy driven by `HeavenTradingBot`.
+///
+/// Strategies receive market and execution events and answer with intents.
+/// Intents go through the bot's own execution and persistence, so a strategy
+/// never signs or submits transactions itself.
+pub trait Strategy: Send {
+    /// Unique name, used to tag trades recorded for this strategy.
+    fn name(&self) -> &str;
+    
+    fn on_event(&mut self, event: &StrategyEvent) -> Vec<StrategyIntent>;
+}
+
+#[derive(Debug, Clone)]
+pub enum StrategyEvent {
+    NewLaunch(TokenLaunch),
+    PriceTick {
+        token_mint: String,
+        price: f64,
+        timestamp: DateTime<Utc>,
+    },
+    TraderTrade {
+        trader: Trader,
+        trade: Trade,
+    },
+    /// Delivered only to the strategy whose intent produced the trade.
+    Fill(Trade),
+}
+
+#[derive(Debug, Clone)]
+pub enum StrategyIntent {
+    Buy {
+        token_mint: String,
+        amount_sol: f64,
+        max_slippage: Option<f64>,
+    },
+    /// Sell `fraction` (0.0-1.0] of this strategy's open positions in `token_mint`.
+    Sell {
+        token_mint: String,
+        fraction: f64,
+        max_slippage: Option<f64>,
+    },
+    /// Drop this strategy's queued intents for `token_mint`.
+    Cancel {
+        token_mint: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct PendingIntent {
+    strategy: String,
+    intent: StrategyIntent,
+    created_at: DateTime<Utc>,
+}
+
+pub struct StrategyRuntime {
+    config: BotConfig,
+    rpc_client: Arc<RpcClient>,
+    heaven_client: Arc<HeavenClient>,
+    database: Arc<Database>,
+    metrics: Arc<Metrics>,
+    positions: Arc<PositionManager>,
+    risk: Arc<RiskEngine>,
+    wallet: Arc<Keypair>,
+    strategies: Mutex<Vec<Box<dyn Strategy>>>,
+    pending_intents: Mutex<VecDeque<PendingIntent>>,
+    event_sender: mpsc::UnboundedSender<StrategyEvent>,
+    event_receiver: Mutex<mpsc::UnboundedReceiver<StrategyEvent>>,
+    last_launch_scan: RwLock<DateTime<Utc>>,
+}
+
+impl StrategyRuntime {
+    pub fn new(
+        config: BotConfig,
+        rpc_client: Arc<RpcClient>,
+        heaven_client: Arc<HeavenClient>,
+        database: Arc<Database>,
+        metrics: Arc<Metrics>,
+        positions: Arc<PositionManager>,
+        risk: Arc<RiskEngine>,
+        wallet: Arc<Keypair>,
+    ) -> Self {
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        
+        Self {
+            config,
+            rpc_client,
+            heaven_client,
+            database,
+            metrics,
+            positions,
+            risk,
+            wallet,
+            strategies: Mutex::new(Vec::new()),
+            pending_intents: Mutex::new(VecDeque::new()),
+            event_sender,
+            event_receiver: Mutex::new(event_receiver),
+            last_launch_scan: RwLock::new(Utc::now()),
+        }
+    }
+    
+    pub async fn register(&self, strategy: Box<dyn Strategy>) -> Result<(), BotError> {
+        let mut strategies = self.strategies.lock().await;
+        if strategies.iter().any(|s| s.name() == strategy.name()) {
+            return Err(BotError::Validation(format!("Strategy already registered: {}", strategy.name())));
+        }
+        
+        info!("Registered strategy: {}", strategy.name());
+        strategies.push(strategy);
+        Ok(())
+    }
+    
+    pub async fn strategy_count(&self) -> usize {
+        self.strategies.lock().await.len()
+    }
+    
+    /// Feed an external event to all registered strategies on the next tick.
+    pub fn publish(&self, event: StrategyEvent) {
+        if self.event_sender.send(event).is_err() {
+            warn!("Strategy runtime is not accepting events");
+        }
+    }
+    
+    pub async fn run(&self, is_running: Arc<RwLock<bool>>) -> Result<(), BotError> {
+        let mut interval = tokio::time::interval(
+            std::time::Duration::from_millis(self.config.strategy_runtime.tick_interval_ms)
+        );
+        
+        while *is_running.read().await {
+            interval.tick().await;
+            
+            if self.risk.is_flattening().await {
+                self.queue_flatten().await;
+            }
+            
+            if self.strategies.lock().await.is_empty() {
+                continue;
+            }
+            
+            if let Err(e) = self.collect_market_events().await {
+                warn!("Failed to collect strategy events: {}", e);
+            }
+            
+            self.dispatch_queued_events().await;
+            
+            if let Err(e) = self.execute_pending_intents().await {
+                warn!("Failed to execute strategy intents: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Strategies holding each token, from the persisted open positions.
+    async fn held_tokens(&self) -> HashMap<String, HashSet<String>> {
+        let mut held: HashMap<String, HashSet<String>> = HashMap::new();
+        for position in self.positions.open_positions_by_source("strategy").await {
+            held.entry(position.token_mint.clone())
+                .or_default()
+                .insert(position.strategy.name().to_string());
+        }
+        held
+    }
+    
+    // Replace whatever strategies queued with full sells of everything they hold
+    async fn queue_flatten(&self) {
+        let held = self.held_tokens().await;
+        let mut pending = self.pending_intents.lock().await;
+        pending.clear();
+        
+        for (token_mint, holders) in held {
+            for strategy in holders {
+                pending.push_back(PendingIntent {
+                    strategy,
+                    intent: StrategyIntent::Sell {
+                        token_mint: token_mint.clone(),
+                        fraction: 1.0,
+                        max_slippage: None,
+                    },
+                    created_at: Utc::now(),
+                });
+            }
+        }
+    }
+    
+    async fn collect_market_events(&self) -> Result<(), BotError> {
+        // New launches since the previous tick; a failed scan is retried next
+        // tick without holding up price ticks and trader trades
+        match self.heaven_client.scan_new_launches().await {
+            Ok(launches) => {
+                let mut last_scan = self.last_launch_scan.write().await;
+                for launch in launches {
+                    if launch.launch_time > *last_scan {
+                        self.publish(StrategyEvent::NewLaunch(launch));
+                    }
+                }
+                *last_scan = Utc::now();
+            }
+            Err(e) => warn!("Failed to scan new launches for strategies: {}", e),
+        }
+        
+        // Price ticks for everything a strategy currently holds
+        let held: Vec<String> = self.held_tokens().await.into_keys().collect();
+        for token_mint in held {
+            match self.heaven_client.get_token_price(&token_mint).await {
+                Ok(price) => self.publish(StrategyEvent::PriceTick {
+                    token_mint,
+                    price,
+                    timestamp: Utc::now(),
+                }),
+                Err(e) => debug!("No price for held token {}: {}", token_mint, e),
+            }
+        }
+        
+        // Trades from tracked traders
+        for trader in self.database.get_tracked_traders().await? {
+            let trades = match self.heaven_client.get_trader_trades(&trader.address).await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    debug!("Failed to fetch trades for {}: {}", trader.address, e);
+                    continue;
+                }
+            };
+            
+            for trade in self.claim_trader_trades(&trader.address, trades).await? {
+                self.publish(StrategyEvent::TraderTrade {
+                    trader: trader.clone(),
+                    trade,
+                });
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Trader trades not yet published, tracked with the persisted watermark
+    /// under a key of our own so the copy trader's claims do not hide them.
+    /// A trader seen for the first time only sets the starting point.
+    async fn claim_trader_trades(&self, trader_address: &str, trades: Vec<Trade>) -> Result<Vec<Trade>, BotError> {
+        let Some(latest) = trades.iter().map(|t| t.timestamp).max() else {
+            return Ok(Vec::new());
+        };
+        
+        let key = format!("strategy:{}", trader_address);
+        let watermark = self.database.get_trader_watermark(&key).await?;
+        
+        let mut new_trades = Vec::new();
+        for trade in trades {
+            if watermark.is_some_and(|w| trade.timestamp < w - Duration::seconds(SEEN_TRADE_WINDOW_SECS)) {
+                continue;
+            }
+            let signature = trade.transaction_signature.clone().unwrap_or_else(|| trade.id.clone());
+            if self.database.mark_trader_trade_seen(&key, &signature, trade.timestamp).await? {
+                new_trades.push(trade);
+            }
+        }
+        
+        self.database.advance_trader_watermark(&key, latest).await?;
+        let high_water = watermark.map_or(latest, |w| w.max(latest));
+        self.database.prune_seen_trader_trades(&key, high_water - Duration::seconds(SEEN_TRADE_WINDOW_SECS)).await?;
+        
+        if watermark.is_none() {
+            return Ok(Vec::new());
+        }
+        new_trades.sort_by_key(|t| t.timestamp);
+        Ok(new_trades)
+    }
+    
+    async fn dispatch_queued_events(&self) {
+        let mut receiver = self.event_receiver.lock().await;
+        let mut strategies = self.strategies.lock().await;
+        let mut pending = self.pending_intents.lock().await;
+        
+        while let Ok(event) = receiver.try_recv() {
+            for strategy in strategies.iter_mut() {
+                let intents = strategy.on_event(&event);
+                Self::queue_intents(&mut pending, strategy.name(), intents);
+            }
+        }
+    }
+    
+    async fn dispatch_fill(&self, strategy_name: &str, trade: &Trade) {
+        let mut strategies = self.strategies.lock().await;
+        let mut pending = self.pending_intents.lock().await;
+        
+        if let Some(strategy) = strategies.iter_mut().find(|s| s.name() == strategy_name) {
+            let intents = strategy.on_event(&StrategyEvent::Fill(trade.clone()));
+            Self::queue_intents(&mut pending, strategy_name, intents);
+        }
+    }
+    
+    fn queue_intents(pending: &mut VecDeque<PendingIntent>, strategy: &str, intents: Vec<StrategyIntent>) {
+        for intent in intents {
+            match &intent {
+                StrategyIntent::Cancel { token_mint } => {
+                    pending.retain(|p| p.strategy != strategy || Self::intent_mint(&p.intent) != token_mint);
+                    debug!("Strategy {} cancelled pending intents for {}", strategy, token_mint);
+                }
+                _ => pending.push_back(PendingIntent {
+                    strategy: strategy.to_string(),
+                    intent,
+                    created_at: Utc::now(),
+                }),
+            }
+        }
+    }
+    
+    fn intent_mint(intent: &StrategyIntent) -> &str {
+        match intent {
+            StrategyIntent::Buy { token_mint, .. }
+            | StrategyIntent::Sell { token_mint, .. }
+            | StrategyIntent::Cancel { token_mint } => token_mint,
+        }
+    }
+    
+    async fn execute_pending_intents(&self) -> Result<(), BotError> {
+        loop {
+            // Pop one at a time so fills can queue follow-up intents
+            let next = self.pending_intents.lock().await.pop_front();
+            let Some(pending) = next else {
+                break;
+            };
+            
+            let result = match &pending.intent {
+                StrategyIntent::Buy { token_mint, amount_sol, max_slippage } => {
+                    self.execute_buy(&pending.strategy, token_mint, *amount_sol, *max_slippage).await
+                }
+                StrategyIntent::Sell { token_mint, fraction, max_slippage } => {
+                    self.execute_sell(&pending.strategy, token_mint, *fraction, *max_slippage).await
+                }
+                StrategyIntent::Cancel { .. } => continue,
+            };
+            
+            match &result {
+                Ok(_) => self.risk.record_trade_result(true).await,
+                // Refused buys never reached the chain
+                Err(BotError::RiskLimit(_)) => {}
+                Err(_) => self.risk.record_trade_result(false).await,
+            }
+            
+            match result {
+                Ok(trade) => {
+                    self.database.record_trade(&trade).await?;
+                    self.track_position(&pending.strategy, &trade).await?;
+                    self.dispatch_fill(&pending.strategy, &trade).await;
+                }
+                Err(e) => {
+                    error!(
+                        "Strategy {} intent for {} failed after {}ms: {}",
+                        pending.strategy,
+                        Self::intent_mint(&pending.intent),
+                        (Utc::now() - pending.created_at).num_milliseconds(),
+                        e
+                    );
+                    self.metrics.record_failed_trade(0.0).await;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn execute_buy(
+        &self,
+        strategy: &str,
+        token_mint: &str,
+        amount_sol: f64,
+        max_slippage: Option<f64>,
+    ) -> Result<Trade, BotError> {
+        if amount_sol <= 0.0 {
+            return Err(BotError::Validation(format!("Invalid buy amount: {}", amount_sol)));
+        }
+        
+        let amount_sol = amount_sol.min(self.config.strategy_runtime.max_sol_per_intent);
+        let slippage = self.intent_slippage(max_slippage);
+        
+        let balance = self.heaven_client.get_sol_balance().await?;
+        if balance < amount_sol {
+            return Err(BotError::InsufficientBalance(
+                format!("Insufficient SOL for strategy buy: {:.4} < {:.4}", balance, amount_sol)
+            ));
+        }
+        
+        let _permit = self.risk.check_buy(&format!("strategy:{}", strategy), token_mint, amount_sol).await?;
+        
+        let quote = self.heaven_client.get_buy_quote(token_mint, amount_sol, slippage).await?;
+        let buy_ix = self.heaven_client.create_buy_instruction(
+            token_mint,
+            amount_sol,
+            quote.token_amount,
+            &self.wallet.pubkey(),
+        ).await?;
+        
+        let signature = self.send_instructions(vec![buy_ix]).await?;
+        
+        let mut trade = Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            token_mint: token_mint.to_string(),
+            trade_type: "buy".to_string(),
+            amount_sol,
+            token_amount: quote.token_amount,
+            price: quote.price,
+            slippage,
+            strategy: SniperStrategy::Custom(strategy.to_string()),
+            timestamp: Utc::now(),
+            status: "pending".to_string(),
+            transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: None,
+        };
+        if let Err(e) = self.heaven_client.settle_trade(&mut trade, &signature.to_string()).await {
+            self.database.record_trade(&trade).await?;
+            return Err(e);
+        }
+        self.metrics.record_successful_trade(trade.amount_sol).await;
+        
+        info!("Strategy {} bought {} for {:.4} SOL", strategy, token_mint, trade.amount_sol);
+        
+        Ok(trade)
+    }
+    
+    async fn execute_sell(
+        &self,
+        strategy: &str,
+        token_mint: &str,
+        fraction: f64,
+        max_slippage: Option<f64>,
+    ) -> Result<Trade, BotError> {
+        if fraction <= 0.0 || fraction > 1.0 {
+            return Err(BotError::Validation(format!("Invalid sell fraction: {}", fraction)));
+        }
+        
+        // Only what this strategy holds; the sniper and copy trader share the wallet
+        let held: f64 = self.positions.open_positions_by_source("strategy").await
+            .iter()
+            .filter(|p| p.token_mint == token_mint && p.strategy.name() == strategy)
+            .map(|p| p.token_amount)
+            .sum();
+        let token_amount = held * fraction;
+        if token_amount <= 0.0 {
+            return Err(BotError::InsufficientBalance(format!("Strategy {} holds no {}", strategy, token_mint)));
+        }
+        
+        let slippage = self.intent_slippage(max_slippage);
+        let quote = self.heaven_client.get_sell_quote(token_mint, token_amount, slippage).await?;
+        let sell_ix = self.heaven_client.create_sell_instruction(
+            token_mint,
+            token_amount,
+            quote.sol_amount,
+            &self.wallet.pubkey(),
+        ).await?;
+        
+        let signature = self.send_instructions(vec![sell_ix]).await?;
+        
+        let mut trade = Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            token_mint: token_mint.to_string(),
+            trade_type: "sell".to_string(),
+            amount_sol: quote.sol_amount,
+            token_amount,
+            price: quote.price,
+            slippage,
+            strategy: SniperStrategy::Custom(strategy.to_string()),
+            timestamp: Utc::now(),
+            status: "pending".to_string(),
+            transaction_signature: None,
+            fee_sol: 0.0,
+            sol_spent: 0.0,
+            sol_received: 0.0,
+            slot: None,
+            token_balance_before: None,
+        };
+        if let Err(e) = self.heaven_client.settle_trade(&mut trade, &signature.to_string()).await {
+            self.database.record_trade(&trade).await?;
+            return Err(e);
+        }
+        self.metrics.record_successful_trade(trade.amount_sol).await;
+        
+        info!("Strategy {} sold {:.0}% of {}", strategy, fraction * 100.0, token_mint);
+        
+        Ok(trade)
+    }
+    
+    async fn track_position(&self, strategy: &str, trade: &Trade) -> Result<(), BotError> {
+        if trade.trade_type == "buy" {
+            return self.positions.open_position(new_position(
+                trade.id.clone(),
+                &trade.token_mint,
+                "strategy",
+                trade.strategy.clone(),
+                trade.price,
+                trade.sol_spent,
+                trade.token_amount,
+            ), trade.fee_sol).await;
+        }
+        
+        // Attribute sells to this strategy's positions in the token, oldest first
+        let mut positions: Vec<_> = self.positions.open_positions_by_source("strategy").await
+            .into_iter()
+            .filter(|p| p.token_mint == trade.token_mint && p.strategy.name() == strategy)
+            .collect();
+        positions.sort_by_key(|p| p.entry_time);
+        
+        let mut remaining_tokens = trade.token_amount;
+        for position in positions {
+            if remaining_tokens <= 0.0 {
+                break;
+            }
+            let sold = remaining_tokens.min(position.token_amount);
+            let share = if trade.token_amount > 0.0 { sold / trade.token_amount } else { 0.0 };
+            self.positions.reduce_position(&position.id, sold, trade.sol_received * share, trade.fee_sol * share).await?;
+            remaining_tokens -= sold;
+        }
+        
+        Ok(())
+    }
+    
+    fn intent_slippage(&self, max_slippage: Option<f64>) -> f64 {
+        max_slippage
+            .unwrap_or(self.config.strategy_runtime.max_slippage)
+            .min(self.config.strategy_runtime.max_slippage)
+    }
+    
+    async fn send_instructions(&self, instructions: Vec<Instruction>) -> Result<Signature, BotError> {
+        let mut all_instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(self.config.heaven.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.heaven.compute_unit_price),
+        ];
+        all_instructions.extend(instructions);
+        
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &all_instructions,
+            Some(&self.wallet.pubkey()),
+            &[&*self.wallet],
+            recent_blockhash,
+        );
+        
+        Ok(self.rpc_client.send_and_confirm_transaction(&transaction)?)
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    config::{ExitRules, SniperConfig, StrategyDefinition},
+    error::BotError,
+    types::{SnipePriority, SniperStrategy, TokenLaunch},
+};
+use tracing::info;
+
+pub struct StrategyRegistry {
+    strategies: Vec<RegisteredStrategy>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisteredStrategy {
+    pub strategy: SniperStrategy,
+    pub definition: StrategyDefinition,
+    filters: Vec<LaunchFilter>,
+}
+
+#[derive(Debug, Clone)]
+struct LaunchFilter {
+    field: LaunchField,
+    op: FilterOp,
+    value: FilterValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LaunchField {
+    Price,
+    InitialPrice,
+    MarketCap,
+    LiquiditySol,
+    Volume24h,
+    FlywheelActivity,
+    TokenType,
+    HasFlywheel,
+    CreatorAddress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterValue {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl StrategyRegistry {
+    pub fn from_config(config: &SniperConfig) -> Result<Self, BotError> {
+        let definitions = if config.strategies.is_empty() {
+            Self::default_definitions(config)
+        } else {
+            config.strategies.clone()
+        };
+        
+        let mut strategies = Vec::new();
+        for definition in definitions {
+            if strategies.iter().any(|s: &RegisteredStrategy| s.definition.name == definition.name) {
+                return Err(BotError::Config(format!("Duplicate sniper strategy: {}", definition.name)));
+            }
+            
+            let filters = definition.filters
+                .iter()
+                .map(|expr| LaunchFilter::parse(expr).map_err(|e| {
+                    BotError::Config(format!("Strategy '{}': {}", definition.name, e))
+                }))
+                .collect::<Result<Vec<_>, _>>()?;
+            
+            info!(
+                "Registered sniper strategy {} ({} filters, enabled: {})",
+                definition.name, filters.len(), definition.enabled
+            );
+            
+            strategies.push(RegisteredStrategy {
+                strategy: SniperStrategy::from_name(&definition.name),
+                definition,
+                filters,
+            });
+        }
+        
+        Ok(Self { strategies })
+    }
+    
+    /// Enabled strategies in declaration order; the first match wins.
+    pub fn enabled(&self) -> impl Iterator<Item = &RegisteredStrategy> {
+        self.strategies.iter().filter(|s| s.definition.enabled)
+    }
+    
+    pub fn get(&self, strategy: &SniperStrategy) -> Option<&RegisteredStrategy> {
+        self.strategies.iter().find(|s| &s.strategy == strategy)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.enabled().count()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    // Equivalent of the strategies that used to be hardcoded in the sniper
+    fn default_definitions(config: &SniperConfig) -> Vec<StrategyDefinition> {
+        let definition = |name: &str, filters: Vec<String>, size_multiplier: f64| StrategyDefinition {
+            name: name.to_string(),
+            enabled: true,
+            filters,
+            size_multiplier,
+            max_slippage: None,
+            sizing: None,
+            exit: ExitRules::default(),
+            priority: SnipePriority::Medium,
+        };
+        
+        vec![
+            definition(
+                "CreatorToken",
+                vec!["token_type == creator".to_string(), "has_flywheel == true".to_string()],
+                1.0,
+            ),
+            definition("CommunityToken", vec!["token_type == community".to_string()], 0.7),
+            definition(
+                "HighVolume",
+                vec![format!("volume_24h > {}", config.volume_threshold * 10.0)],
+                1.2,
+            ),
+            definition("LowMarketCap", vec!["market_cap < 10000".to_string()], 0.8),
+            definition(
+                "FlywheelActive",
+                vec!["has_flywheel == true".to_string(), "flywheel_activity > 0".to_string()],
+                1.1,
+            ),
+        ]
+    }
+}
+
+impl RegisteredStrategy {
+    pub fn matches(&self, launch: &TokenLaunch) -> bool {
+        self.filters.iter().all(|filter| filter.matches(launch))
+    }
+    
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+}
+
+impl LaunchFilter {
+    fn parse(expr: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = expr.split_whitespace().collect();
+        if parts.len() < 3 {
+            return Err(format!("Invalid filter '{}', expected 'field op value'", expr));
+        }
+        
+        let field = match parts[0] {
+            "price" => LaunchField::Price,
+            "initial_price" => LaunchField::InitialPrice,
+            "market_cap" => LaunchField::MarketCap,
+            "liquidity_sol" => LaunchField::LiquiditySol,
+            "volume_24h" => LaunchField::Volume24h,
+            "flywheel_activity" => LaunchField::FlywheelActivity,
+            "token_type" => LaunchField::TokenType,
+            "has_flywheel" => LaunchField::HasFlywheel,
+            "creator_address" => LaunchField::CreatorAddress,
+            other => return Err(format!("Unknown launch field '{}'", other)),
+        };
+        
+        let op = match parts[1] {
+            "<" => FilterOp::Lt,
+            "<=" => FilterOp::Le,
+            ">" => FilterOp::Gt,
+            ">=" => FilterOp::Ge,
+            "==" => FilterOp::Eq,
+            "!=" => FilterOp::Ne,
+            other => return Err(format!("Unknown operator '{}'", other)),
+        };
+        
+        // Allow string values with spaces and optional quotes
+        let raw_value = parts[2..].join(" ");
+        let raw_value = raw_value.trim_matches(|c| c == '"' || c == '\'');
+        
+        let value = match field {
+            LaunchField::TokenType | LaunchField::CreatorAddress => {
+                if !matches!(op, FilterOp::Eq | FilterOp::Ne) {
+                    return Err(format!("Field '{}' only supports == and !=", parts[0]));
+                }
+                FilterValue::Text(raw_value.to_string())
+            }
+            LaunchField::HasFlywheel => {
+                if !matches!(op, FilterOp::Eq | FilterOp::Ne) {
+                    return Err(format!("Field '{}' only supports == and !=", parts[0]));
+                }
+                let flag = raw_value.parse::<bool>()
+                    .map_err(|_| format!("Expected true or false, got '{}'", raw_value))?;
+                FilterValue::Bool(flag)
+            }
+            _ => {
+                let number = raw_value.parse::<f64>()
+                    .map_err(|_| format!("Expected a number, got '{}'", raw_value))?;
+                FilterValue::Number(number)
+            }
+        };
+        
+        Ok(Self { field, op, value })
+    }
+    
+    fn matches(&self, launch: &TokenLaunch) -> bool {
+        match (&self.value, self.field) {
+            (FilterValue::Number(expected), field) => {
+                let actual = match field {
+                    LaunchField::Price => launch.price,
+                    LaunchField::InitialPrice => launch.initial_price,
+                    LaunchField::MarketCap => launch.market_cap,
+                    LaunchField::LiquiditySol => launch.liquidity_sol,
+                    LaunchField::Volume24h => launch.volume_24h,
+                    LaunchField::FlywheelActivity => launch.flywheel_activity,
+                    _ => return false,
+                };
+                self.compare_numbers(actual, *expected)
+            }
+            (FilterValue::Bool(expected), LaunchField::HasFlywheel) => {
+                self.compare_eq(launch.has_flywheel == *expected)
+            }
+            (FilterValue::Text(expected), LaunchField::TokenType) => {
+                self.compare_eq(launch.token_type == *expected)
+            }
+            (FilterValue::Text(expected), LaunchField::CreatorAddress) => {
+                self.compare_eq(launch.creator_address.as_deref() == Some(expected.as_str()))
+            }
+            _ => false,
+        }
+    }
+    
+    fn compare_numbers(&self, actual: f64, expected: f64) -> bool {
+        match self.op {
+            FilterOp::Lt => actual < expected,
+            FilterOp::Le => actual <= expected,
+            FilterOp::Gt => actual > expected,
+            FilterOp::Ge => actual >= expected,
+            FilterOp::Eq => actual == expected,
+            FilterOp::Ne => actual != expected,
+        }
+    }
+    
+    fn compare_eq(&self, equal: bool) -> bool {
+        match self.op {
+            FilterOp::Eq => equal,
+            FilterOp::Ne => !equal,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    error::BotError,
+    heaven_client::HeavenClient,
+    types::Trade,
+};
+use futures::StreamExt;
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{info, warn, debug};
+use chrono::{DateTime, Utc};
+
+// A confirmed swap can take a moment to be readable with getTransaction
+const DECODE_ATTEMPTS: u32 = 3;
+const DECODE_RETRY_DELAY_MS: u64 = 400;
+
+/// A decoded swap by a tracked wallet.
+#[derive(Debug, Clone)]
+pub struct LeaderSwap {
+    pub trader_address: String,
+    pub trade: Trade,
+    /// When the swap's logs arrived, before it was read back
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Where trader subscriptions connect and what they listen for.
+#[derive(Debug, Clone)]
+pub struct StreamSettings {
+    pub ws_url: String,
+    pub program_id: String,
+    pub reconnect_delay: Duration,
+}
+
+/// One WebSocket connection shared by a `logsSubscribe` subscription per
+/// tracked wallet. Every Heaven swap a wallet makes is sent as soon as it is
+/// confirmed. The connection is reopened when it drops.
+pub struct TraderStream {
+    settings: StreamSettings,
+    heaven_client: Arc<HeavenClient>,
+    sender: mpsc::UnboundedSender<LeaderSwap>,
+    client: Mutex<Option<Arc<PubsubClient>>>,
+}
+
+/// A running subscription for one wallet.
+pub struct TraderSubscription {
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl TraderSubscription {
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+    
+    /// Unsubscribe and end the task.
+    pub fn close(self) {
+        // A task that already ended has nothing to unsubscribe
+        let _ = self.stop.send(());
+    }
+}
+
+impl TraderStream {
+    pub fn new(
+        settings: StreamSettings,
+        heaven_client: Arc<HeavenClient>,
+        sender: mpsc::UnboundedSender<LeaderSwap>,
+    ) -> Self {
+        Self {
+            settings,
+            heaven_client,
+            sender,
+            client: Mutex::new(None),
+        }
+    }
+    
+    /// Follow `trader_address` until the subscription is closed or the
+    /// receiving side is gone, resubscribing after connection drops.
+    pub fn subscribe(self: &Arc<Self>, trader_address: String) -> TraderSubscription {
+        let (stop, mut stopped) = oneshot::channel();
+        let stream = self.clone();
+        
+        let handle = tokio::spawn(async move {
+            while !stream.sender.is_closed() {
+                match stream.stream_trader_swaps(&trader_address, &mut stopped).await {
+                    Ok(true) => return,
+                    Ok(false) => warn!("Subscription for {} ended", trader_address),
+                    Err(e) => warn!("Subscription for {} dropped: {}", trader_address, e),
+                }
+                
+                tokio::select! {
+                    _ = &mut stopped => return,
+                    _ = tokio::time::sleep(stream.settings.reconnect_delay) => {}
+                }
+            }
+        });
+        
+        TraderSubscription { stop, handle }
+    }
+    
+    // The shared connection, opened on first use and after a drop
+    async fn client(&self) -> Result<Arc<PubsubClient>, BotError> {
+        let mut client = self.client.lock().await;
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+        
+        let connected = Arc::new(PubsubClient::new(&self.settings.ws_url).await
+            .map_err(|e| BotError::Network(format!("WebSocket connect failed: {}", e)))?);
+        info!("Connected trader stream to {}", self.settings.ws_url);
+        *client = Some(connected.clone());
+        Ok(connected)
+    }
+    
+    // Drop a connection that failed, unless another subscription already replaced it
+    async fn reset(&self, failed: &Arc<PubsubClient>) {
+        let mut client = self.client.lock().await;
+        if client.as_ref().is_some_and(|current| Arc::ptr_eq(current, failed)) {
+            *client = None;
+        }
+    }
+    
+    /// Returns `Ok(true)` once stopped, `Ok(false)` when the subscription ended on its own.
+    async fn stream_trader_swaps(
+        &self,
+        trader_address: &str,
+        stopped: &mut oneshot::Receiver<()>,
+    ) -> Result<bool, BotError> {
+        let client = self.client().await?;
+        let subscribed = client.logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![trader_address.to_string()]),
+            RpcTransactionLogsConfig {
+                // Swaps are read back with getTransaction, which needs confirmed
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        ).await;
+        let (mut logs, unsubscribe) = match subscribed {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                self.reset(&client).await;
+                return Err(BotError::Network(format!("logsSubscribe failed: {}", e)));
+            }
+        };
+        
+        info!("Streaming swaps for trader {}", trader_address);
+        
+        loop {
+            let response = tokio::select! {
+                _ = &mut *stopped => {
+                    unsubscribe().await;
+                    return Ok(true);
+                }
+                response = logs.next() => response,
+            };
+            let Some(response) = response else {
+                break;
+            };
+            
+            let detected_at = Utc::now();
+            let logs = response.value;
+            if logs.err.is_some() || !invokes_program(&logs.logs, &self.settings.program_id) {
+                continue;
+            }
+            
+            match self.decode_swap(&logs.signature, trader_address).await {
+                Ok(Some(trade)) => {
+                    debug!("Trader {} swapped {} in {}", trader_address, trade.trade_type, trade.token_mint);
+                    let swap = LeaderSwap {
+                        trader_address: trader_address.to_string(),
+                        trade,
+                        detected_at,
+                    };
+                    if self.sender.send(swap).is_err() {
+                        unsubscribe().await;
+                        return Ok(true);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to decode {} for {}: {}", logs.signature, trader_address, e),
+            }
+        }
+        
+        // The stream only ends when the connection went away
+        self.reset(&client).await;
+        Ok(false)
+    }
+    
+    // Reads the transaction on the blocking pool, retrying until it is readable
+    async fn decode_swap(&self, signature: &str, trader_address: &str) -> Result<Option<Trade>, BotError> {
+        let mut attempt = 1;
+        loop {
+            let heaven_client = self.heaven_client.clone();
+            let (owned_signature, owned_trader) = (signature.to_string(), trader_address.to_string());
+            let decoded = tokio::task::spawn_blocking(move || {
+                heaven_client.decode_leader_swap(&owned_signature, &owned_trader)
+            }).await
+                .map_err(|e| BotError::Network(format!("Decode task failed: {}", e)))?;
+            
+            match decoded {
+                Err(e) if attempt < DECODE_ATTEMPTS => {
+                    debug!("Retrying decode of {} (attempt {}): {}", signature, attempt, e);
+                    tokio::time::sleep(Duration::from_millis(DECODE_RETRY_DELAY_MS)).await;
+                    attempt += 1;
+                }
+                decoded => return decoded,
+            }
+        }
+    }
+}
+
+fn invokes_program(logs: &[String], program_id: &str) -> bool {
+    let invoke = format!("Program {} invoke", program_id);
+    logs.iter().any(|line| line.starts_with(&invoke))
+}
@@ -50,6 +50,66 @@ pub struct Trade {
     pub timestamp: DateTime<Utc>,
     pub status: String,
     pub transaction_signature: Option<String>,
+    #[serde(default)]
+    pub fee_sol: f64, // network and priority fees paid
+    #[serde(default)]
+    pub sol_spent: f64, // SOL that left the wallet, fees included
+    #[serde(default)]
+    pub sol_received: f64, // SOL that arrived in the wallet, net of fees
+    #[serde(default)]
+    pub slot: Option<u64>, // slot the transaction landed in, once filled
+    #[serde(default)]
+    pub token_balance_before: Option<f64>, // the wallet's holding of the token before this trade, when decoded
+}
+
+impl Trade {
+    /// Replace quoted amounts with what the confirmed transaction actually did.
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        self.amount_sol = fill.swap_sol();
+        self.token_amount = fill.token_amount();
+        self.price = fill.effective_price();
+        self.fee_sol = fill.fee_sol;
+        self.sol_spent = (-fill.sol_delta).max(0.0);
+        self.sol_received = fill.sol_delta.max(0.0);
+        self.slot = Some(fill.slot);
+        self.status = "executed".to_string();
+        self.transaction_signature = Some(fill.signature.clone());
+    }
+    
+    /// Landed, but what it spent or received is unknown until the fill is read.
+    pub fn mark_unsettled(&mut self, signature: &str) {
+        self.status = "unsettled".to_string();
+        self.transaction_signature = Some(signature.to_string());
+    }
+}
+
+/// Wallet balance changes of a confirmed swap, read from the transaction meta.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    pub signature: String,
+    pub slot: u64,
+    pub token_delta: f64, // positive for buys
+    pub sol_delta: f64, // net wallet SOL change, fees included, token account rent excluded
+    pub fee_sol: f64,
+}
+
+impl Fill {
+    pub fn token_amount(&self) -> f64 {
+        self.token_delta.abs()
+    }
+    
+    /// SOL paid into or taken out of the pool, excluding the network fee.
+    pub fn swap_sol(&self) -> f64 {
+        (self.sol_delta + self.fee_sol).abs()
+    }
+    
+    pub fn effective_price(&self) -> f64 {
+        if self.token_delta != 0.0 {
+            self.swap_sol() / self.token_amount()
+        } else {
+            0.0
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +120,9 @@ pub struct TradeQuote {
     pub slippage: f64,
     pub fee: f64,
     pub fee_pct: f64,
+    /// How far the average fill price is from the pool's spot price, as a fraction
+    #[serde(default)]
+    pub price_impact: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,7 +135,7 @@ pub struct TradeResult {
 }
 
 // Sniper Types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SniperStrategy {
     CreatorToken,
     CommunityToken,
@@ -82,6 +145,31 @@ pub enum SniperStrategy {
     Custom(String),
 }
 
+impl SniperStrategy {
+    /// Stable name used in config tables and database rows.
+    pub fn name(&self) -> &str {
+        match self {
+            SniperStrategy::CreatorToken => "CreatorToken",
+            SniperStrategy::CommunityToken => "CommunityToken",
+            SniperStrategy::HighVolume => "HighVolume",
+            SniperStrategy::LowMarketCap => "LowMarketCap",
+            SniperStrategy::FlywheelActive => "FlywheelActive",
+            SniperStrategy::Custom(name) => name,
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "CreatorToken" => SniperStrategy::CreatorToken,
+            "CommunityToken" => SniperStrategy::CommunityToken,
+            "HighVolume" => SniperStrategy::HighVolume,
+            "LowMarketCap" => SniperStrategy::LowMarketCap,
+            "FlywheelActive" => SniperStrategy::FlywheelActive,
+            _ => SniperStrategy::Custom(name.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnipeTarget {
     pub token_mint: String,
@@ -92,9 +180,10 @@ pub struct SnipeTarget {
     pub conditions: Vec<SnipeCondition>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SnipePriority {
     Low,
+    #[default]
     Medium,
     High,
     Critical,
@@ -111,6 +200,48 @@ pub enum SnipeCondition {
     Custom(String),
 }
 
+/// A launch by a known creator, watched for its outcome.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatorLaunch {
+    pub token_mint: String,
+    pub creator_address: String,
+    pub launch_time: DateTime<Utc>,
+    pub initial_price: f64,
+    pub peak_price: f64,
+    /// Tokens the creator held when we first saw the launch
+    pub creator_initial_tokens: f64,
+    /// Share of those tokens the creator has since sold
+    pub creator_sold_share: f64,
+    pub rugged_at: Option<DateTime<Utc>>,
+    /// No longer watched; counts towards the creator's reputation
+    pub settled: bool,
+}
+
+impl CreatorLaunch {
+    pub fn peak_multiple(&self) -> f64 {
+        if self.initial_price > 0.0 { self.peak_price / self.initial_price } else { 1.0 }
+    }
+    
+    pub fn secs_to_rug(&self) -> Option<i64> {
+        self.rugged_at.map(|rugged_at| (rugged_at - self.launch_time).num_seconds())
+    }
+}
+
+/// A creator's record over their settled launches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatorReputation {
+    pub address: String,
+    pub launches: u64,
+    pub rugs: u64,
+    pub average_peak_multiple: f64,
+    pub average_sold_share: f64,
+    pub average_secs_to_rug: Option<f64>,
+    /// Between 0 (serial rugger) and 1
+    pub score: f64,
+    pub blacklisted: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
 // Copy Trading Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trader {
@@ -127,6 +258,58 @@ pub struct Trader {
     pub risk_score: f64,
 }
 
+/// A wallet whose buys are treated as a signal to stay out of a token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FadedWallet {
+    pub address: String,
+    pub realized_pnl: f64,
+    pub round_trips: u64,
+    pub win_rate: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Where a tracked trader stands after evaluating our copies of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraderStatus {
+    Active,
+    /// Still copied, at a reduced size
+    Probation,
+    /// No longer copied
+    Blacklisted,
+}
+
+impl TraderStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TraderStatus::Active => "active",
+            TraderStatus::Probation => "probation",
+            TraderStatus::Blacklisted => "blacklisted",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "probation" => TraderStatus::Probation,
+            "blacklisted" => TraderStatus::Blacklisted,
+            _ => TraderStatus::Active,
+        }
+    }
+}
+
+/// One entry of a trader's status history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraderStatusChange {
+    pub trader_address: String,
+    pub from_status: TraderStatus,
+    pub to_status: TraderStatus,
+    pub reason: String,
+    /// Closed copies and their return behind an automatic change
+    pub copies: u64,
+    pub copy_return: f64,
+    pub win_rate: f64,
+    pub changed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopyTrade {
     pub id: String,
@@ -142,6 +325,87 @@ pub struct CopyTrade {
     pub timestamp: DateTime<Utc>,
     pub status: String,
     pub transaction_signature: Option<String>,
+    #[serde(default)]
+    pub fee_sol: f64,
+    #[serde(default)]
+    pub execution: CopyExecution,
+}
+
+impl CopyTrade {
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        self.amount_sol = fill.swap_sol();
+        self.token_amount = fill.token_amount();
+        self.price = fill.effective_price();
+        self.fee_sol = fill.fee_sol;
+        self.transaction_signature = Some(fill.signature.clone());
+        self.execution.landed_slot = Some(fill.slot);
+        self.execution.fill_price = self.price;
+    }
+    
+    pub fn mark_unsettled(&mut self, signature: &str) {
+        self.status = "unsettled".to_string();
+        self.transaction_signature = Some(signature.to_string());
+    }
+}
+
+/// How a copy was executed compared with the leader's own swap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CopyExecution {
+    pub leader_signature: Option<String>,
+    pub leader_slot: Option<u64>,
+    pub leader_price: f64,
+    /// When we first saw the leader's swap
+    pub detected_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub landed_slot: Option<u64>,
+    pub fill_price: f64,
+}
+
+impl CopyExecution {
+    pub fn from_leader(trade: &Trade, detected_at: DateTime<Utc>) -> Self {
+        Self {
+            leader_signature: trade.transaction_signature.clone(),
+            leader_slot: trade.slot,
+            leader_price: trade.price,
+            detected_at: Some(detected_at),
+            ..Self::default()
+        }
+    }
+    
+    /// Price slippage against the leader's fill, positive when ours was worse.
+    pub fn slippage_vs_leader(&self, trade_type: &str) -> Option<f64> {
+        if self.leader_price <= 0.0 || self.fill_price <= 0.0 {
+            return None;
+        }
+        
+        let difference = (self.fill_price - self.leader_price) / self.leader_price;
+        Some(if trade_type == "buy" { difference } else { -difference })
+    }
+    
+    /// Slots between the leader's swap and ours.
+    pub fn slot_lag(&self) -> Option<u64> {
+        Some(self.landed_slot?.saturating_sub(self.leader_slot?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopySlippageGroup {
+    Trader,
+    Token,
+}
+
+/// Copy fills against their leaders' fills for one trader or token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopySlippageBreakdown {
+    pub key: String,
+    pub copies: u64,
+    pub average_slippage: f64,
+    pub worst_slippage: f64,
+    /// SOL lost to filling at worse prices than the leader
+    pub slippage_cost_sol: f64,
+    /// From seeing the leader's swap to sending ours
+    pub average_reaction_ms: f64,
+    pub average_slot_lag: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -171,6 +435,10 @@ pub struct BundleTransaction {
     pub fee_payer: String,
     pub compute_units: u32,
     pub priority_fee: u64,
+    pub trade_type: String, // "buy" or "sell"
+    pub token_mint: String,
+    /// SOL spent by a buy, checked against risk limits
+    pub amount_sol: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -312,6 +580,66 @@ pub struct Position {
     pub strategy: SniperStrategy,
     pub stop_loss: Option<f64>,
     pub take_profit: Option<f64>,
+    pub source: String, // "sniper", "copy_trader" or "strategy"
+    pub trader_address: Option<String>,
+    pub realized_pnl: f64,
+    pub status: String, // "open" or "closed"
+    pub initial_token_amount: f64,
+    pub high_water_price: f64,
+    pub take_profits_hit: u32,
+}
+
+// PnL Ledger Types
+/// One buy in the cost-basis ledger. Lots share their id with the position
+/// they were opened for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lot {
+    pub id: String,
+    pub token_mint: String,
+    pub source: String,
+    pub strategy: SniperStrategy,
+    pub trader_address: Option<String>,
+    pub token_amount: f64,
+    pub remaining_tokens: f64,
+    pub cost_sol: f64, // SOL spent including fees
+    pub remaining_cost_sol: f64,
+    pub fee_sol: f64,
+    pub entry_time: DateTime<Utc>,
+    pub status: String, // "open" or "closed"
+}
+
+/// The part of a sell matched against one lot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotClosure {
+    pub id: String,
+    pub lot_id: String,
+    pub token_mint: String,
+    pub strategy: SniperStrategy,
+    pub trader_address: Option<String>,
+    pub tokens: f64,
+    pub cost_sol: f64,
+    pub proceeds_sol: f64, // net of the sell's fees
+    pub fee_sol: f64, // buy and sell fees attributed to this closure
+    pub realized_pnl: f64,
+    pub closed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PnlGroup {
+    Token,
+    Strategy,
+    Trader,
+    Day,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlBreakdown {
+    pub key: String,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub fees_sol: f64,
+    pub closures: u64,
+    pub open_lots: u64,
 }
 
 // Performance Tracking Types
@@ -329,6 +657,19 @@ pub struct PerformanceMetrics {
     pub sharpe_ratio: f64,
     pub total_volume: f64,
     pub last_updated: DateTime<Utc>,
+    pub sortino_ratio: f64,
+    pub best_trade_pnl: f64,
+    pub worst_trade_pnl: f64,
+    pub total_fees: f64,
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+/// Cumulative realized PnL after a closed trade, and the drawdown from its running peak.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub equity: f64,
+    pub drawdown: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -418,6 +759,7 @@ pub enum EventType {
     NewTokenLaunch,
     PriceAlert,
     RiskLimitExceeded,
+    TraderStatusChanged,
     BotStarted,
     BotStopped,
     Error,